@@ -3,7 +3,8 @@
 #[cfg(test)]
 use stdsimd_test::assert_instr;
 use simd_llvm::simd_add;
-use v128::f64x2;
+use v64::i16x4;
+use v128::{f32x4, f64x2, u8x16};
 
 /// Vector add.
 #[inline(always)]
@@ -37,6 +38,43 @@ pub unsafe fn vaddd_u64(a: u64, b: u64) -> u64 {
     a + b
 }
 
+#[allow(improper_ctypes)]
+extern "C" {
+    #[link_name = "llvm.aarch64.neon.tbl1.v16i8"]
+    fn vqtbl1q(a: u8x16, b: u8x16) -> u8x16;
+    #[link_name = "llvm.aarch64.neon.vcvthf2fp"]
+    fn vcvthf2fp(a: i16x4) -> f32x4;
+    #[link_name = "llvm.aarch64.neon.vcvtfp2hf"]
+    fn vcvtfp2hf(a: f32x4) -> i16x4;
+}
+
+/// Table look-up: lane `i` of the result is `t[idx[i]]`, or zero if
+/// `idx[i]` is out of range.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(tbl))]
+pub unsafe fn vqtbl1q_u8(t: u8x16, idx: u8x16) -> u8x16 {
+    vqtbl1q(t, idx)
+}
+
+/// Convert four half-precision values, stored as their bit patterns, to
+/// single precision.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(fcvtl))]
+pub unsafe fn vcvt_f32_f16(a: i16x4) -> f32x4 {
+    vcvthf2fp(a)
+}
+
+/// Convert four single-precision values to half precision, rounding to
+/// nearest with ties to even, and return their bit patterns.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(fcvtn))]
+pub unsafe fn vcvt_f16_f32(a: f32x4) -> i16x4 {
+    vcvtfp2hf(a)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,4 +114,36 @@ mod tests {
         let r = unsafe { vaddd_u64(a, b) };
         assert_eq!(r, e);
     }
+
+    #[test]
+    fn vqtbl1q_u8_() {
+        let t = u8x16::new(
+            10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+        );
+        let idx = u8x16::new(
+            15, 0, 1, 16, 255, 128, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11,
+        );
+        let e = u8x16::new(
+            25, 10, 11, 0, 0, 0, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+        );
+        let r = unsafe { vqtbl1q_u8(t, idx) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vcvt_f32_f16_() {
+        let a = i16x4::new(0x3c00, -0x4000, 0x3800, 0x7bff);
+        let e = f32x4::new(1., -2., 0.5, 65504.);
+        let r = unsafe { vcvt_f32_f16(a) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vcvt_f16_f32_() {
+        // 1 + 2^-11 is halfway between 1.0 and the next half.
+        let a = f32x4::new(1., -2., 1.00048828125, 1e6);
+        let e = i16x4::new(0x3c00, -0x4000, 0x3c00, 0x7c00);
+        let r = unsafe { vcvt_f16_f32(a) };
+        assert_eq!(r, e);
+    }
 }
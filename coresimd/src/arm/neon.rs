@@ -3,7 +3,7 @@
 #[cfg(test)]
 use stdsimd_test::assert_instr;
 
-use simd_llvm::simd_add;
+use simd_llvm::{simd_add, simd_shuffle16, simd_shuffle8};
 
 use v64::{f32x2, i16x4, i32x2, i8x8, u16x4, u32x2, u8x8};
 use v128::{f32x4, i16x8, i32x4, i64x2, i8x16, u16x8, u32x4, u64x2, u8x16};
@@ -202,6 +202,210 @@ extern "C" {
     // supported in v7/A32/A64:
     #[link_name = "llvm.aarch64.neon.frsqrte.v2f32"]
     fn frsqrte_v2f32(a: f32x2) -> f32x2;
+
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vabdu.v8i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uabd.v8i8")]
+    fn vabdu_v8i8(a: u8x8, b: u8x8) -> u8x8;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vabdu.v16i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uabd.v16i8")]
+    fn vabdu_v16i8(a: u8x16, b: u8x16) -> u8x16;
+
+    #[cfg_attr(target_arch = "arm",
+               link_name = "llvm.arm.neon.vpaddlu.v8i16.v16i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uaddlp.v8i16.v16i8")]
+    fn vpaddlu_v8i16_v16i8(a: u8x16) -> u16x8;
+    #[cfg_attr(target_arch = "arm",
+               link_name = "llvm.arm.neon.vpaddlu.v4i32.v8i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uaddlp.v4i32.v8i16")]
+    fn vpaddlu_v4i32_v8i16(a: u16x8) -> u32x4;
+    #[cfg_attr(target_arch = "arm",
+               link_name = "llvm.arm.neon.vpaddlu.v2i64.v4i32")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uaddlp.v2i64.v4i32")]
+    fn vpaddlu_v2i64_v4i32(a: u32x4) -> u64x2;
+
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vrhaddu.v8i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.urhadd.v8i8")]
+    fn vrhaddu_v8i8(a: u8x8, b: u8x8) -> u8x8;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vrhaddu.v16i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.urhadd.v16i8")]
+    fn vrhaddu_v16i8(a: u8x16, b: u8x16) -> u8x16;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vrhaddu.v4i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.urhadd.v4i16")]
+    fn vrhaddu_v4i16(a: u16x4, b: u16x4) -> u16x4;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vrhaddu.v8i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.urhadd.v8i16")]
+    fn vrhaddu_v8i16(a: u16x8, b: u16x8) -> u16x8;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vhaddu.v8i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uhadd.v8i8")]
+    fn vhaddu_v8i8(a: u8x8, b: u8x8) -> u8x8;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vhaddu.v16i8")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uhadd.v16i8")]
+    fn vhaddu_v16i8(a: u8x16, b: u8x16) -> u8x16;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vhaddu.v4i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uhadd.v4i16")]
+    fn vhaddu_v4i16(a: u16x4, b: u16x4) -> u16x4;
+    #[cfg_attr(target_arch = "arm", link_name = "llvm.arm.neon.vhaddu.v8i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.uhadd.v8i16")]
+    fn vhaddu_v8i16(a: u16x8, b: u16x8) -> u16x8;
+    #[cfg_attr(target_arch = "arm",
+               link_name = "llvm.arm.neon.vqrdmulh.v4i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.sqrdmulh.v4i16")]
+    fn vqrdmulh_v4i16(a: i16x4, b: i16x4) -> i16x4;
+    #[cfg_attr(target_arch = "arm",
+               link_name = "llvm.arm.neon.vqrdmulh.v8i16")]
+    #[cfg_attr(target_arch = "aarch64",
+               link_name = "llvm.aarch64.neon.sqrdmulh.v8i16")]
+    fn vqrdmulh_v8i16(a: i16x8, b: i16x8) -> i16x8;
+}
+
+/// Absolute difference between the arguments.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uabd))]
+pub unsafe fn vabd_u8(a: u8x8, b: u8x8) -> u8x8 {
+    vabdu_v8i8(a, b)
+}
+
+/// Absolute difference between the arguments.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uabd))]
+pub unsafe fn vabdq_u8(a: u8x16, b: u8x16) -> u8x16 {
+    vabdu_v16i8(a, b)
+}
+
+/// Absolute difference long.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uabdl))]
+pub unsafe fn vabdl_u8(a: u8x8, b: u8x8) -> u16x8 {
+    vabdu_v8i8(a, b).as_u16x8()
+}
+
+/// Unsigned add long pairwise.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uaddlp))]
+pub unsafe fn vpaddlq_u8(a: u8x16) -> u16x8 {
+    vpaddlu_v8i16_v16i8(a)
+}
+
+/// Unsigned add long pairwise.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uaddlp))]
+pub unsafe fn vpaddlq_u16(a: u16x8) -> u32x4 {
+    vpaddlu_v4i32_v8i16(a)
+}
+
+/// Unsigned add long pairwise.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uaddlp))]
+pub unsafe fn vpaddlq_u32(a: u32x4) -> u64x2 {
+    vpaddlu_v2i64_v4i32(a)
+}
+
+/// Unsigned add and accumulate long pairwise.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uadalp))]
+pub unsafe fn vpadalq_u16(a: u32x4, b: u16x8) -> u32x4 {
+    simd_add(a, vpaddlq_u16(b))
+}
+
+/// Rounding halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(urhadd))]
+pub unsafe fn vrhadd_u8(a: u8x8, b: u8x8) -> u8x8 {
+    vrhaddu_v8i8(a, b)
+}
+
+/// Rounding halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(urhadd))]
+pub unsafe fn vrhaddq_u8(a: u8x16, b: u8x16) -> u8x16 {
+    vrhaddu_v16i8(a, b)
+}
+
+/// Rounding halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(urhadd))]
+pub unsafe fn vrhadd_u16(a: u16x4, b: u16x4) -> u16x4 {
+    vrhaddu_v4i16(a, b)
+}
+
+/// Rounding halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(urhadd))]
+pub unsafe fn vrhaddq_u16(a: u16x8, b: u16x8) -> u16x8 {
+    vrhaddu_v8i16(a, b)
+}
+
+/// Halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uhadd))]
+pub unsafe fn vhadd_u8(a: u8x8, b: u8x8) -> u8x8 {
+    vhaddu_v8i8(a, b)
+}
+
+/// Halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uhadd))]
+pub unsafe fn vhaddq_u8(a: u8x16, b: u8x16) -> u8x16 {
+    vhaddu_v16i8(a, b)
+}
+
+/// Halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uhadd))]
+pub unsafe fn vhadd_u16(a: u16x4, b: u16x4) -> u16x4 {
+    vhaddu_v4i16(a, b)
+}
+
+/// Halving add.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(uhadd))]
+pub unsafe fn vhaddq_u16(a: u16x8, b: u16x8) -> u16x8 {
+    vhaddu_v8i16(a, b)
+}
+
+/// Signed saturating rounding doubling multiply returning high half.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(sqrdmulh))]
+pub unsafe fn vqrdmulh_s16(a: i16x4, b: i16x4) -> i16x4 {
+    vqrdmulh_v4i16(a, b)
+}
+
+/// Signed saturating rounding doubling multiply returning high half.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(sqrdmulh))]
+pub unsafe fn vqrdmulhq_s16(a: i16x8, b: i16x8) -> i16x8 {
+    vqrdmulh_v8i16(a, b)
 }
 
 /// Reciprocal square-root estimate.
@@ -212,6 +416,66 @@ pub unsafe fn vrsqrte_f32(a: f32x2) -> f32x2 {
     frsqrte_v2f32(a)
 }
 
+/// Reverse the order of the bytes within each 16-bit element.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(rev16))]
+pub unsafe fn vrev16_u8(a: u8x8) -> u8x8 {
+    simd_shuffle8(a, a, [1, 0, 3, 2, 5, 4, 7, 6])
+}
+
+/// Reverse the order of the bytes within each 16-bit element.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(rev16))]
+pub unsafe fn vrev16q_u8(a: u8x16) -> u8x16 {
+    simd_shuffle16(
+        a,
+        a,
+        [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+    )
+}
+
+/// Reverse the order of the bytes within each 32-bit element.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(rev32))]
+pub unsafe fn vrev32_u8(a: u8x8) -> u8x8 {
+    simd_shuffle8(a, a, [3, 2, 1, 0, 7, 6, 5, 4])
+}
+
+/// Reverse the order of the bytes within each 32-bit element.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(rev32))]
+pub unsafe fn vrev32q_u8(a: u8x16) -> u8x16 {
+    simd_shuffle16(
+        a,
+        a,
+        [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12],
+    )
+}
+
+/// Reverse the order of the bytes within each 64-bit element.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(rev64))]
+pub unsafe fn vrev64_u8(a: u8x8) -> u8x8 {
+    simd_shuffle8(a, a, [7, 6, 5, 4, 3, 2, 1, 0])
+}
+
+/// Reverse the order of the bytes within each 64-bit element.
+#[inline(always)]
+#[target_feature = "+neon"]
+#[cfg_attr(test, assert_instr(rev64))]
+pub unsafe fn vrev64q_u8(a: u8x16) -> u8x16 {
+    simd_shuffle16(
+        a,
+        a,
+        [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,4 +673,144 @@ mod tests {
         let r = unsafe { vrsqrte_f32(a) };
         assert_eq!(r, e);
     }
+
+    #[test]
+    fn vabd_u8_() {
+        let a = u8x8::new(1, 2, 3, 4, 255, 0, 7, 8);
+        let b = u8x8::new(8, 7, 6, 5, 0, 255, 7, 1);
+        let e = u8x8::new(7, 5, 3, 1, 255, 255, 0, 7);
+        let r = unsafe { vabd_u8(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vabdq_u8_() {
+        let a = u8x16::new(1, 2, 3, 4, 255, 0, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8);
+        let b = u8x16::new(8, 7, 6, 5, 0, 255, 7, 1, 8, 7, 6, 5, 4, 3, 2, 1);
+        let e = u8x16::new(7, 5, 3, 1, 255, 255, 0, 7, 7, 5, 3, 1, 1, 3, 5, 7);
+        let r = unsafe { vabdq_u8(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vabdl_u8_() {
+        let a = u8x8::new(1, 2, 3, 4, 255, 0, 7, 8);
+        let b = u8x8::new(8, 7, 6, 5, 0, 255, 7, 1);
+        let e = u16x8::new(7, 5, 3, 1, 255, 255, 0, 7);
+        let r = unsafe { vabdl_u8(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vpaddlq_u8_() {
+        let a = u8x16::new(
+            255, 255, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14,
+        );
+        let e = u16x8::new(510, 3, 7, 11, 15, 19, 23, 27);
+        let r = unsafe { vpaddlq_u8(a) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vpaddlq_u16_() {
+        let a = u16x8::new(65535, 65535, 1, 2, 3, 4, 5, 6);
+        let e = u32x4::new(131070, 3, 7, 11);
+        let r = unsafe { vpaddlq_u16(a) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vpaddlq_u32_() {
+        let a = u32x4::new(4294967295, 4294967295, 1, 2);
+        let e = u64x2::new(8589934590, 3);
+        let r = unsafe { vpaddlq_u32(a) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vpadalq_u16_() {
+        let a = u32x4::new(1, 2, 3, 4);
+        let b = u16x8::new(1, 2, 3, 4, 5, 6, 7, 8);
+        let e = u32x4::new(4, 9, 14, 19);
+        let r = unsafe { vpadalq_u16(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vrhadd_u8_() {
+        let a = u8x8::new(1, 2, 3, 4, 255, 0, 255, 8);
+        let b = u8x8::new(2, 2, 6, 5, 255, 1, 254, 1);
+        let e = u8x8::new(2, 2, 5, 5, 255, 1, 255, 5);
+        let r = unsafe { vrhadd_u8(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vrhaddq_u16_() {
+        let a = u16x8::new(1, 2, 3, 4, 65535, 0, 65535, 8);
+        let b = u16x8::new(2, 2, 6, 5, 65535, 1, 65534, 1);
+        let e = u16x8::new(2, 2, 5, 5, 65535, 1, 65535, 5);
+        let r = unsafe { vrhaddq_u16(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vhadd_u8_() {
+        let a = u8x8::new(1, 2, 3, 4, 255, 0, 255, 8);
+        let b = u8x8::new(2, 2, 6, 5, 255, 1, 254, 1);
+        let e = u8x8::new(1, 2, 4, 4, 255, 0, 254, 4);
+        let r = unsafe { vhadd_u8(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vhaddq_u16_() {
+        let a = u16x8::new(1, 2, 3, 4, 65535, 0, 65535, 8);
+        let b = u16x8::new(2, 2, 6, 5, 65535, 1, 65534, 1);
+        let e = u16x8::new(1, 2, 4, 4, 65535, 0, 65534, 4);
+        let r = unsafe { vhaddq_u16(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vqrdmulhq_s16_() {
+        let a = i16x8::new(16384, -16384, 32767, -32768, -32768, 3, 0, 1);
+        let b = i16x8::new(16384, 16384, 32767, -32768, 32767, 16384, 5, 1);
+        let e = i16x8::new(8192, -8192, 32766, 32767, -32767, 2, 0, 0);
+        let r = unsafe { vqrdmulhq_s16(a, b) };
+        assert_eq!(r, e);
+    }
+
+    #[test]
+    fn vrev16_u8_() {
+        let a = u8x8::new(0, 1, 2, 3, 4, 5, 6, 7);
+        let e = u8x8::new(1, 0, 3, 2, 5, 4, 7, 6);
+        assert_eq!(unsafe { vrev16_u8(a) }, e);
+    }
+
+    #[test]
+    fn vrev32q_u8_() {
+        let a = u8x16::new(
+            0, 1, 2, 3, 4, 5, 6, 7,
+            8, 9, 10, 11, 12, 13, 14, 15,
+        );
+        let e = u8x16::new(
+            3, 2, 1, 0, 7, 6, 5, 4,
+            11, 10, 9, 8, 15, 14, 13, 12,
+        );
+        assert_eq!(unsafe { vrev32q_u8(a) }, e);
+    }
+
+    #[test]
+    fn vrev64q_u8_() {
+        let a = u8x16::new(
+            0, 1, 2, 3, 4, 5, 6, 7,
+            8, 9, 10, 11, 12, 13, 14, 15,
+        );
+        let e = u8x16::new(
+            7, 6, 5, 4, 3, 2, 1, 0,
+            15, 14, 13, 12, 11, 10, 9, 8,
+        );
+        assert_eq!(unsafe { vrev64q_u8(a) }, e);
+    }
 }
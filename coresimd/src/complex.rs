@@ -0,0 +1,105 @@
+//! Interleaved complex arithmetic used by the `c32x2`, `c32x4`, `c64x1`
+//! and `c64x2` types.
+//!
+//! The complex lanes are stored as `[re, im, re, im, ...]`. A product
+//! `(a + bi)(c + di)` is computed as `[a, a] * [c, d] -+ [b, b] * [d, c]`:
+//! the duplications and the swap are shuffles (`movsldup`, `movshdup`,
+//! `shufps` on x86), and the alternating subtract/add is `fmaddsub` when
+//! FMA is enabled at compile-time, or `addsubps`/`addsubpd` when SSE3 (AVX
+//! for the 256-bit types) is.
+
+use simd_llvm::{simd_shuffle2, simd_shuffle4, simd_shuffle8};
+use v128::{f32x4, f64x2};
+use v256::{f32x8, f64x4};
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+          target_feature = "fma"))]
+#[allow(improper_ctypes)]
+extern "C" {
+    #[link_name = "llvm.x86.fma.vfmaddsub.ps"]
+    fn vfmaddsubps(a: f32x4, b: f32x4, c: f32x4) -> f32x4;
+    #[link_name = "llvm.x86.fma.vfmaddsub.ps.256"]
+    fn vfmaddsubps256(a: f32x8, b: f32x8, c: f32x8) -> f32x8;
+    #[link_name = "llvm.x86.fma.vfmaddsub.pd"]
+    fn vfmaddsubpd(a: f64x2, b: f64x2, c: f64x2) -> f64x2;
+    #[link_name = "llvm.x86.fma.vfmaddsub.pd.256"]
+    fn vfmaddsubpd256(a: f64x4, b: f64x4, c: f64x4) -> f64x4;
+}
+
+macro_rules! complex_fns {
+    ($(($fty:ident, $shuffle:ident, $mul:ident, $conj:ident, $mul_i:ident,
+        $addsub_feature:tt: $addsub:ident, $fmaddsub:ident,
+        re: $re:expr, im: $im:expr, swap: $swap:expr,
+        neg_re: $neg_re:expr, neg_im: $neg_im:expr)),+) => {
+        $(
+            /// Complex product of the interleaved lanes of `a` and `b`.
+            #[inline(always)]
+            pub fn $mul(a: $fty, b: $fty) -> $fty {
+                let (re, im, swap): ($fty, $fty, $fty) = unsafe {
+                    (
+                        $shuffle(a, a, $re),
+                        $shuffle(a, a, $im),
+                        $shuffle(b, b, $swap),
+                    )
+                };
+                #[cfg(all(any(target_arch = "x86",
+                              target_arch = "x86_64"),
+                          target_feature = "fma"))]
+                {
+                    unsafe { $fmaddsub(re, b, im * swap) }
+                }
+                #[cfg(all(any(target_arch = "x86",
+                              target_arch = "x86_64"),
+                          not(target_feature = "fma"),
+                          target_feature = $addsub_feature))]
+                {
+                    unsafe { ::vendor::$addsub(re * b, im * swap) }
+                }
+                #[cfg(not(all(any(target_arch = "x86",
+                                  target_arch = "x86_64"),
+                              any(target_feature = "fma",
+                                  target_feature = $addsub_feature))))]
+                {
+                    re * b + im * swap * $neg_re
+                }
+            }
+
+            /// Complex conjugate of the interleaved lanes of `a`.
+            #[inline(always)]
+            pub fn $conj(a: $fty) -> $fty {
+                a * $neg_im
+            }
+
+            /// Multiplies the interleaved lanes of `a` by `i`.
+            #[inline(always)]
+            pub fn $mul_i(a: $fty) -> $fty {
+                let swap: $fty = unsafe { $shuffle(a, a, $swap) };
+                swap * $neg_re
+            }
+        )+
+    }
+}
+
+complex_fns!(
+    (f32x4, simd_shuffle4, mul_f32x4, conj_f32x4, mul_i_f32x4,
+     "sse3": _mm_addsub_ps, vfmaddsubps,
+     re: [0, 0, 2, 2], im: [1, 1, 3, 3], swap: [1, 0, 3, 2],
+     neg_re: f32x4::new(-1., 1., -1., 1.),
+     neg_im: f32x4::new(1., -1., 1., -1.)),
+    (f32x8, simd_shuffle8, mul_f32x8, conj_f32x8, mul_i_f32x8,
+     "avx": _mm256_addsub_ps, vfmaddsubps256,
+     re: [0, 0, 2, 2, 4, 4, 6, 6], im: [1, 1, 3, 3, 5, 5, 7, 7],
+     swap: [1, 0, 3, 2, 5, 4, 7, 6],
+     neg_re: f32x8::new(-1., 1., -1., 1., -1., 1., -1., 1.),
+     neg_im: f32x8::new(1., -1., 1., -1., 1., -1., 1., -1.)),
+    (f64x2, simd_shuffle2, mul_f64x2, conj_f64x2, mul_i_f64x2,
+     "sse3": _mm_addsub_pd, vfmaddsubpd,
+     re: [0, 0], im: [1, 1], swap: [1, 0],
+     neg_re: f64x2::new(-1., 1.),
+     neg_im: f64x2::new(1., -1.)),
+    (f64x4, simd_shuffle4, mul_f64x4, conj_f64x4, mul_i_f64x4,
+     "avx": _mm256_addsub_pd, vfmaddsubpd256,
+     re: [0, 0, 2, 2], im: [1, 1, 3, 3], swap: [1, 0, 3, 2],
+     neg_re: f64x4::new(-1., 1., -1., 1.),
+     neg_im: f64x4::new(1., -1., 1., -1.))
+);
@@ -0,0 +1,232 @@
+//! Lookup tables for the `compress` and `expand` operations of the portable
+//! vector types.
+//!
+//! Entry `bits` of a table describes the lane permutation for the mask
+//! whose `i`-th lane is set iff bit `i` of `bits` is set. Nibble `i` of an
+//! entry is the index of the source lane moved into lane `i`, or `8` if lane
+//! `i` of the result is zero.
+//!
+//! The nibble form is expanded into a `pshufb`/`tbl` byte shuffle or a
+//! `vpermd` index vector by the functions below. The tables themselves are
+//! checked against their definition in the tests of this module.
+
+use core::mem;
+
+use simd_llvm::{simd_cast, simd_shr};
+use v128::{u16x8, u32x4, u8x16};
+use v256::u32x8;
+
+/// `compress` permutations for vectors with 4 lanes.
+pub static COMPRESS_4: [u16; 16] = [
+    0x8888, 0x8880, 0x8881, 0x8810, 0x8882, 0x8820, 0x8821, 0x8210,
+    0x8883, 0x8830, 0x8831, 0x8310, 0x8832, 0x8320, 0x8321, 0x3210,
+];
+
+/// `expand` permutations for vectors with 4 lanes.
+pub static EXPAND_4: [u16; 16] = [
+    0x8888, 0x8880, 0x8808, 0x8810, 0x8088, 0x8180, 0x8108, 0x8210,
+    0x0888, 0x1880, 0x1808, 0x2810, 0x1088, 0x2180, 0x2108, 0x3210,
+];
+
+/// `compress` permutations for vectors with 8 lanes.
+pub static COMPRESS_8: [u32; 256] = [
+    0x88888888, 0x88888880, 0x88888881, 0x88888810, 0x88888882, 0x88888820,
+    0x88888821, 0x88888210, 0x88888883, 0x88888830, 0x88888831, 0x88888310,
+    0x88888832, 0x88888320, 0x88888321, 0x88883210, 0x88888884, 0x88888840,
+    0x88888841, 0x88888410, 0x88888842, 0x88888420, 0x88888421, 0x88884210,
+    0x88888843, 0x88888430, 0x88888431, 0x88884310, 0x88888432, 0x88884320,
+    0x88884321, 0x88843210, 0x88888885, 0x88888850, 0x88888851, 0x88888510,
+    0x88888852, 0x88888520, 0x88888521, 0x88885210, 0x88888853, 0x88888530,
+    0x88888531, 0x88885310, 0x88888532, 0x88885320, 0x88885321, 0x88853210,
+    0x88888854, 0x88888540, 0x88888541, 0x88885410, 0x88888542, 0x88885420,
+    0x88885421, 0x88854210, 0x88888543, 0x88885430, 0x88885431, 0x88854310,
+    0x88885432, 0x88854320, 0x88854321, 0x88543210, 0x88888886, 0x88888860,
+    0x88888861, 0x88888610, 0x88888862, 0x88888620, 0x88888621, 0x88886210,
+    0x88888863, 0x88888630, 0x88888631, 0x88886310, 0x88888632, 0x88886320,
+    0x88886321, 0x88863210, 0x88888864, 0x88888640, 0x88888641, 0x88886410,
+    0x88888642, 0x88886420, 0x88886421, 0x88864210, 0x88888643, 0x88886430,
+    0x88886431, 0x88864310, 0x88886432, 0x88864320, 0x88864321, 0x88643210,
+    0x88888865, 0x88888650, 0x88888651, 0x88886510, 0x88888652, 0x88886520,
+    0x88886521, 0x88865210, 0x88888653, 0x88886530, 0x88886531, 0x88865310,
+    0x88886532, 0x88865320, 0x88865321, 0x88653210, 0x88888654, 0x88886540,
+    0x88886541, 0x88865410, 0x88886542, 0x88865420, 0x88865421, 0x88654210,
+    0x88886543, 0x88865430, 0x88865431, 0x88654310, 0x88865432, 0x88654320,
+    0x88654321, 0x86543210, 0x88888887, 0x88888870, 0x88888871, 0x88888710,
+    0x88888872, 0x88888720, 0x88888721, 0x88887210, 0x88888873, 0x88888730,
+    0x88888731, 0x88887310, 0x88888732, 0x88887320, 0x88887321, 0x88873210,
+    0x88888874, 0x88888740, 0x88888741, 0x88887410, 0x88888742, 0x88887420,
+    0x88887421, 0x88874210, 0x88888743, 0x88887430, 0x88887431, 0x88874310,
+    0x88887432, 0x88874320, 0x88874321, 0x88743210, 0x88888875, 0x88888750,
+    0x88888751, 0x88887510, 0x88888752, 0x88887520, 0x88887521, 0x88875210,
+    0x88888753, 0x88887530, 0x88887531, 0x88875310, 0x88887532, 0x88875320,
+    0x88875321, 0x88753210, 0x88888754, 0x88887540, 0x88887541, 0x88875410,
+    0x88887542, 0x88875420, 0x88875421, 0x88754210, 0x88887543, 0x88875430,
+    0x88875431, 0x88754310, 0x88875432, 0x88754320, 0x88754321, 0x87543210,
+    0x88888876, 0x88888760, 0x88888761, 0x88887610, 0x88888762, 0x88887620,
+    0x88887621, 0x88876210, 0x88888763, 0x88887630, 0x88887631, 0x88876310,
+    0x88887632, 0x88876320, 0x88876321, 0x88763210, 0x88888764, 0x88887640,
+    0x88887641, 0x88876410, 0x88887642, 0x88876420, 0x88876421, 0x88764210,
+    0x88887643, 0x88876430, 0x88876431, 0x88764310, 0x88876432, 0x88764320,
+    0x88764321, 0x87643210, 0x88888765, 0x88887650, 0x88887651, 0x88876510,
+    0x88887652, 0x88876520, 0x88876521, 0x88765210, 0x88887653, 0x88876530,
+    0x88876531, 0x88765310, 0x88876532, 0x88765320, 0x88765321, 0x87653210,
+    0x88887654, 0x88876540, 0x88876541, 0x88765410, 0x88876542, 0x88765420,
+    0x88765421, 0x87654210, 0x88876543, 0x88765430, 0x88765431, 0x87654310,
+    0x88765432, 0x87654320, 0x87654321, 0x76543210,
+];
+
+/// `expand` permutations for vectors with 8 lanes.
+pub static EXPAND_8: [u32; 256] = [
+    0x88888888, 0x88888880, 0x88888808, 0x88888810, 0x88888088, 0x88888180,
+    0x88888108, 0x88888210, 0x88880888, 0x88881880, 0x88881808, 0x88882810,
+    0x88881088, 0x88882180, 0x88882108, 0x88883210, 0x88808888, 0x88818880,
+    0x88818808, 0x88828810, 0x88818088, 0x88828180, 0x88828108, 0x88838210,
+    0x88810888, 0x88821880, 0x88821808, 0x88832810, 0x88821088, 0x88832180,
+    0x88832108, 0x88843210, 0x88088888, 0x88188880, 0x88188808, 0x88288810,
+    0x88188088, 0x88288180, 0x88288108, 0x88388210, 0x88180888, 0x88281880,
+    0x88281808, 0x88382810, 0x88281088, 0x88382180, 0x88382108, 0x88483210,
+    0x88108888, 0x88218880, 0x88218808, 0x88328810, 0x88218088, 0x88328180,
+    0x88328108, 0x88438210, 0x88210888, 0x88321880, 0x88321808, 0x88432810,
+    0x88321088, 0x88432180, 0x88432108, 0x88543210, 0x80888888, 0x81888880,
+    0x81888808, 0x82888810, 0x81888088, 0x82888180, 0x82888108, 0x83888210,
+    0x81880888, 0x82881880, 0x82881808, 0x83882810, 0x82881088, 0x83882180,
+    0x83882108, 0x84883210, 0x81808888, 0x82818880, 0x82818808, 0x83828810,
+    0x82818088, 0x83828180, 0x83828108, 0x84838210, 0x82810888, 0x83821880,
+    0x83821808, 0x84832810, 0x83821088, 0x84832180, 0x84832108, 0x85843210,
+    0x81088888, 0x82188880, 0x82188808, 0x83288810, 0x82188088, 0x83288180,
+    0x83288108, 0x84388210, 0x82180888, 0x83281880, 0x83281808, 0x84382810,
+    0x83281088, 0x84382180, 0x84382108, 0x85483210, 0x82108888, 0x83218880,
+    0x83218808, 0x84328810, 0x83218088, 0x84328180, 0x84328108, 0x85438210,
+    0x83210888, 0x84321880, 0x84321808, 0x85432810, 0x84321088, 0x85432180,
+    0x85432108, 0x86543210, 0x08888888, 0x18888880, 0x18888808, 0x28888810,
+    0x18888088, 0x28888180, 0x28888108, 0x38888210, 0x18880888, 0x28881880,
+    0x28881808, 0x38882810, 0x28881088, 0x38882180, 0x38882108, 0x48883210,
+    0x18808888, 0x28818880, 0x28818808, 0x38828810, 0x28818088, 0x38828180,
+    0x38828108, 0x48838210, 0x28810888, 0x38821880, 0x38821808, 0x48832810,
+    0x38821088, 0x48832180, 0x48832108, 0x58843210, 0x18088888, 0x28188880,
+    0x28188808, 0x38288810, 0x28188088, 0x38288180, 0x38288108, 0x48388210,
+    0x28180888, 0x38281880, 0x38281808, 0x48382810, 0x38281088, 0x48382180,
+    0x48382108, 0x58483210, 0x28108888, 0x38218880, 0x38218808, 0x48328810,
+    0x38218088, 0x48328180, 0x48328108, 0x58438210, 0x38210888, 0x48321880,
+    0x48321808, 0x58432810, 0x48321088, 0x58432180, 0x58432108, 0x68543210,
+    0x10888888, 0x21888880, 0x21888808, 0x32888810, 0x21888088, 0x32888180,
+    0x32888108, 0x43888210, 0x21880888, 0x32881880, 0x32881808, 0x43882810,
+    0x32881088, 0x43882180, 0x43882108, 0x54883210, 0x21808888, 0x32818880,
+    0x32818808, 0x43828810, 0x32818088, 0x43828180, 0x43828108, 0x54838210,
+    0x32810888, 0x43821880, 0x43821808, 0x54832810, 0x43821088, 0x54832180,
+    0x54832108, 0x65843210, 0x21088888, 0x32188880, 0x32188808, 0x43288810,
+    0x32188088, 0x43288180, 0x43288108, 0x54388210, 0x32180888, 0x43281880,
+    0x43281808, 0x54382810, 0x43281088, 0x54382180, 0x54382108, 0x65483210,
+    0x32108888, 0x43218880, 0x43218808, 0x54328810, 0x43218088, 0x54328180,
+    0x54328108, 0x65438210, 0x43210888, 0x54321880, 0x54321808, 0x65432810,
+    0x54321088, 0x65432180, 0x65432108, 0x76543210,
+];
+
+/// Splits the packed nibbles of `idx` into 8 lanes.
+#[inline(always)]
+fn nibbles8(idx: u32) -> u32x8 {
+    let shifts = u32x8::new(0, 4, 8, 12, 16, 20, 24, 28);
+    unsafe { simd_shr(u32x8::splat(idx), shifts) & u32x8::splat(0xf) }
+}
+
+/// Byte shuffle moving the 32-bit lanes of a 128-bit vector as described by
+/// the table entry `idx`. Zeroed lanes get indices with the high bit set,
+/// which both `pshufb` and `tbl` map to zero.
+#[inline(always)]
+pub fn shuffle_bytes_4x32(idx: u32) -> u8x16 {
+    let shifts = u32x4::new(0, 4, 8, 12);
+    let n = unsafe { simd_shr(u32x4::splat(idx), shifts) } & u32x4::splat(0xf);
+    let zero = n >> 3;
+    let b = n * u32x4::splat(0x0404_0404) + u32x4::splat(0x0302_0100)
+        | zero * u32x4::splat(0x8080_8080);
+    unsafe { mem::transmute(b) }
+}
+
+/// Byte shuffle moving the 16-bit lanes of a 128-bit vector as described by
+/// the table entry `idx`. Zeroed lanes get indices with the high bit set,
+/// which both `pshufb` and `tbl` map to zero.
+#[inline(always)]
+pub fn shuffle_bytes_8x16(idx: u32) -> u8x16 {
+    let n: u16x8 = unsafe { simd_cast(nibbles8(idx)) };
+    let zero = n >> 3;
+    let b = n * u16x8::splat(0x0202) + u16x8::splat(0x0100)
+        | zero * u16x8::splat(0x8080);
+    unsafe { mem::transmute(b) }
+}
+
+/// Lane permutation for `vpermd` described by the table entry `idx`, and
+/// the mask of the lanes that are kept (all ones) or zeroed.
+#[inline(always)]
+pub fn permute_8x32(idx: u32) -> (u32x8, u32x8) {
+    let n = nibbles8(idx);
+    let keep = (n >> 3) - u32x8::splat(1);
+    (n & u32x8::splat(7), keep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compress(n: u32, bits: u32) -> u32 {
+        let mut r = 0;
+        let mut j = 0;
+        for i in 0..n {
+            if bits & (1 << i) != 0 {
+                r |= i << (4 * j);
+                j += 1;
+            }
+        }
+        for k in j..n {
+            r |= 8 << (4 * k);
+        }
+        r
+    }
+
+    fn expand(n: u32, bits: u32) -> u32 {
+        let mut r = 0;
+        let mut j = 0;
+        for i in 0..n {
+            if bits & (1 << i) != 0 {
+                r |= j << (4 * i);
+                j += 1;
+            } else {
+                r |= 8 << (4 * i);
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn tables() {
+        for bits in 0..16 {
+            assert_eq!(u32::from(COMPRESS_4[bits]), compress(4, bits as u32));
+            assert_eq!(u32::from(EXPAND_4[bits]), expand(4, bits as u32));
+        }
+        for bits in 0..256 {
+            assert_eq!(COMPRESS_8[bits], compress(8, bits as u32));
+            assert_eq!(EXPAND_8[bits], expand(8, bits as u32));
+        }
+    }
+
+    #[test]
+    fn shuffles() {
+        // lanes 1 and 3 set: [1, 3, zero, zero]
+        let b = shuffle_bytes_4x32(u32::from(COMPRESS_4[0b1010]));
+        let e = u8x16::new(
+            4, 5, 6, 7, 12, 13, 14, 15, 160, 161, 162, 163, 160, 161, 162, 163,
+        );
+        assert_eq!(b, e);
+
+        // lanes 0 and 7 set: [0, 7, zero, ...]
+        let b = shuffle_bytes_8x16(COMPRESS_8[0b1000_0001]);
+        let e = u8x16::new(
+            0, 1, 14, 15, 144, 145, 144, 145, 144, 145, 144, 145, 144, 145,
+            144, 145,
+        );
+        assert_eq!(b, e);
+
+        let (p, keep) = permute_8x32(COMPRESS_8[0b0110_0000]);
+        assert_eq!(p, u32x8::new(5, 6, 0, 0, 0, 0, 0, 0));
+        assert_eq!(keep, u32x8::new(!0, !0, 0, 0, 0, 0, 0, 0));
+    }
+}
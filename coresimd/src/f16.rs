@@ -0,0 +1,390 @@
+//! IEEE 754 binary16 (half-precision) conversions used by the `f16x4`,
+//! `f16x8` and `f16x16` storage types.
+//!
+//! Widening is exact. Narrowing rounds according to a `RoundingMode`. F16C
+//! is used on x86 when it is detected at run-time, and the NEON `fcvtl` /
+//! `fcvtn` instructions are used on AArch64 for round-to-nearest. Otherwise
+//! the lanes are converted in software. The software conversions produce
+//! the same bits as the hardware ones, including the quieting of signaling
+//! NaNs.
+
+use core::mem::transmute;
+
+use simd_llvm::{simd_shuffle16, simd_shuffle4, simd_shuffle8};
+use v64::u16x4;
+use v128::{f32x4, u16x8};
+use v256::{f32x8, u16x16};
+use v512::f32x16;
+
+/// Rounding mode of a narrowing floating-point conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even.
+    Nearest,
+    /// Round toward negative infinity.
+    Down,
+    /// Round toward positive infinity.
+    Up,
+    /// Round toward zero.
+    TowardZero,
+}
+
+/// Converts the binary16 value with bit pattern `h` to `f32`.
+#[inline]
+pub fn f16_to_f32(h: u16) -> f32 {
+    let sign = u32::from(h & 0x8000) << 16;
+    let exp = u32::from(h >> 10) & 0x1f;
+    let man = u32::from(h) & 0x3ff;
+    let bits = match exp {
+        // Zero or subnormal: `man * 2^-24` is exact in `f32`.
+        0 => {
+            let x = man as f32 * (1. / 16_777_216.);
+            sign | unsafe { transmute::<f32, u32>(x) }
+        }
+        0x1f if man == 0 => sign | 0x7f80_0000,
+        0x1f => sign | 0x7fc0_0000 | man << 13,
+        _ => sign | (exp + 112) << 23 | man << 13,
+    };
+    unsafe { transmute(bits) }
+}
+
+/// Whether `q + rem / (2 * half)` is rounded up (away from zero) to
+/// `q + 1`, where `rem < 2 * half`.
+#[inline]
+fn round_away(
+    q: u32, rem: u32, half: u32, neg: bool, mode: RoundingMode
+) -> bool {
+    match mode {
+        RoundingMode::Nearest => rem > half || (rem == half && q & 1 == 1),
+        RoundingMode::Down => neg && rem != 0,
+        RoundingMode::Up => !neg && rem != 0,
+        RoundingMode::TowardZero => false,
+    }
+}
+
+/// Converts `x` to the bit pattern of the nearest binary16 value in the
+/// direction of `mode`.
+#[inline]
+pub fn f32_to_f16(x: f32, mode: RoundingMode) -> u16 {
+    let bits: u32 = unsafe { transmute(x) };
+    let sign = (bits >> 16) as u16 & 0x8000;
+    let neg = sign != 0;
+    let abs = bits & 0x7fff_ffff;
+
+    if abs > 0x7f80_0000 {
+        // NaN: keep the upper payload bits and quiet it.
+        return sign | 0x7e00 | (abs >> 13) as u16 & 0x3ff;
+    }
+    if abs == 0x7f80_0000 {
+        return sign | 0x7c00;
+    }
+
+    let (q, rem, half) = if abs >= 0x4780_0000 {
+        // |x| >= 65536 lies more than half an ulp above the largest finite
+        // half, 65504; rounding it away from zero gives infinity.
+        (0x7bff, 3, 2)
+    } else if abs >= 0x3880_0000 {
+        // Normal half: rebias the exponent and drop 13 mantissa bits. A
+        // carry out of the mantissa correctly bumps the exponent.
+        ((abs >> 13) - (112 << 10), abs & 0x1fff, 0x1000)
+    } else {
+        // Subnormal half: count in units of 2^-24.
+        let exp = abs >> 23;
+        let (man, exp) = if exp == 0 {
+            (abs, 1)
+        } else {
+            ((abs & 0x7f_ffff) | 0x80_0000, exp)
+        };
+        let shift = 126 - exp;
+        if shift > 24 {
+            // Less than half a unit, but not necessarily zero.
+            (0, if man == 0 { 0 } else { 1 }, 2)
+        } else {
+            (man >> shift, man & ((1 << shift) - 1), 1 << (shift - 1))
+        }
+    };
+    let inc = round_away(q, rem, half, neg, mode) as u32;
+    sign | (q + inc) as u16
+}
+
+#[cfg(not(all(target_arch = "aarch64", target_feature = "neon")))]
+macro_rules! widen_portable {
+    ($a:expr, $fty:ident) => {
+        {
+            let a = $a;
+            let mut r = $fty::splat(0.);
+            for (d, &h) in r.as_mut_array().iter_mut()
+                .zip(a.as_array().iter())
+            {
+                *d = f16_to_f32(h);
+            }
+            r
+        }
+    }
+}
+
+macro_rules! narrow_portable {
+    ($a:expr, $mode:expr, $bits:ident) => {
+        {
+            let a = $a;
+            let mut r = $bits::splat(0);
+            for (d, &x) in r.as_mut_array().iter_mut()
+                .zip(a.as_array().iter())
+            {
+                *d = f32_to_f16(x, $mode);
+            }
+            r
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod f16c {
+    use simd_llvm::{simd_shuffle4, simd_shuffle8};
+    use v64::{i16x4, u16x4};
+    use v128::{f32x4, i16x8, u16x8};
+    use v256::f32x8;
+
+    use super::RoundingMode;
+
+    #[inline]
+    fn imm8(mode: RoundingMode) -> i32 {
+        match mode {
+            RoundingMode::Nearest => 0,
+            RoundingMode::Down => 1,
+            RoundingMode::Up => 2,
+            RoundingMode::TowardZero => 3,
+        }
+    }
+
+    #[target_feature = "+f16c"]
+    pub unsafe fn widen_x4(a: u16x4) -> f32x4 {
+        let a: u16x8 = simd_shuffle8(a, a, [0, 1, 2, 3, 4, 5, 6, 7]);
+        ::vendor::_mm_cvtph_ps(i16x8::from(a))
+    }
+
+    #[target_feature = "+f16c"]
+    pub unsafe fn narrow_x4(a: f32x4, mode: RoundingMode) -> u16x4 {
+        let r = ::vendor::_mm_cvtps_ph(a, imm8(mode));
+        let r: i16x4 = simd_shuffle4(r, r, [0, 1, 2, 3]);
+        u16x4::from(r)
+    }
+
+    #[target_feature = "+f16c"]
+    pub unsafe fn widen_x8(a: u16x8) -> f32x8 {
+        ::vendor::_mm256_cvtph_ps(i16x8::from(a))
+    }
+
+    #[target_feature = "+f16c"]
+    pub unsafe fn narrow_x8(a: f32x8, mode: RoundingMode) -> u16x8 {
+        u16x8::from(::vendor::_mm256_cvtps_ph(a, imm8(mode)))
+    }
+}
+
+/// Widens four half-precision lanes to `f32`.
+#[inline]
+pub fn widen_x4(a: u16x4) -> f32x4 {
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        use v64::i16x4;
+        unsafe { ::vendor::vcvt_f32_f16(i16x4::from(a)) }
+    }
+    #[cfg(not(all(target_arch = "aarch64", target_feature = "neon")))]
+    {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if cfg_feature_enabled!("f16c") {
+                return unsafe { f16c::widen_x4(a) };
+            }
+        }
+        widen_portable!(a, f32x4)
+    }
+}
+
+/// Narrows four `f32` lanes to half precision.
+#[inline]
+pub fn narrow_x4(a: f32x4, mode: RoundingMode) -> u16x4 {
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        // `fcvtn` rounds according to FPCR, which is round-to-nearest
+        // unless changed.
+        if mode == RoundingMode::Nearest {
+            return u16x4::from(unsafe { ::vendor::vcvt_f16_f32(a) });
+        }
+    }
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if cfg_feature_enabled!("f16c") {
+            return unsafe { f16c::narrow_x4(a, mode) };
+        }
+    }
+    narrow_portable!(a, mode, u16x4)
+}
+
+/// Widens eight half-precision lanes to `f32`.
+#[inline]
+pub fn widen_x8(a: u16x8) -> f32x8 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if cfg_feature_enabled!("f16c") {
+            return unsafe { f16c::widen_x8(a) };
+        }
+    }
+    unsafe {
+        let lo: u16x4 = simd_shuffle4(a, a, [0, 1, 2, 3]);
+        let hi: u16x4 = simd_shuffle4(a, a, [4, 5, 6, 7]);
+        let (lo, hi) = (widen_x4(lo), widen_x4(hi));
+        simd_shuffle8(lo, hi, [0, 1, 2, 3, 4, 5, 6, 7])
+    }
+}
+
+/// Narrows eight `f32` lanes to half precision.
+#[inline]
+pub fn narrow_x8(a: f32x8, mode: RoundingMode) -> u16x8 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if cfg_feature_enabled!("f16c") {
+            return unsafe { f16c::narrow_x8(a, mode) };
+        }
+    }
+    unsafe {
+        let lo: f32x4 = simd_shuffle4(a, a, [0, 1, 2, 3]);
+        let hi: f32x4 = simd_shuffle4(a, a, [4, 5, 6, 7]);
+        let (lo, hi) = (narrow_x4(lo, mode), narrow_x4(hi, mode));
+        simd_shuffle8(lo, hi, [0, 1, 2, 3, 4, 5, 6, 7])
+    }
+}
+
+/// Widens sixteen half-precision lanes to `f32`.
+#[inline]
+pub fn widen_x16(a: u16x16) -> f32x16 {
+    unsafe {
+        let lo: u16x8 = simd_shuffle8(a, a, [0, 1, 2, 3, 4, 5, 6, 7]);
+        let hi: u16x8 = simd_shuffle8(a, a, [8, 9, 10, 11, 12, 13, 14, 15]);
+        let (lo, hi) = (widen_x8(lo), widen_x8(hi));
+        simd_shuffle16(
+            lo,
+            hi,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        )
+    }
+}
+
+/// Narrows sixteen `f32` lanes to half precision.
+#[inline]
+pub fn narrow_x16(a: f32x16, mode: RoundingMode) -> u16x16 {
+    unsafe {
+        let lo: f32x8 = simd_shuffle8(a, a, [0, 1, 2, 3, 4, 5, 6, 7]);
+        let hi: f32x8 = simd_shuffle8(a, a, [8, 9, 10, 11, 12, 13, 14, 15]);
+        let (lo, hi) = (narrow_x8(lo, mode), narrow_x8(hi, mode));
+        simd_shuffle16(
+            lo,
+            hi,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::transmute;
+
+    use super::*;
+
+    const MODES: [RoundingMode; 4] = [
+        RoundingMode::Nearest,
+        RoundingMode::Down,
+        RoundingMode::Up,
+        RoundingMode::TowardZero,
+    ];
+
+    fn bits(x: f32) -> u32 {
+        unsafe { transmute(x) }
+    }
+
+    /// The value of a finite half, computed independently in `f64`.
+    fn value(h: u16) -> f64 {
+        let exp = u32::from(h >> 10) & 0x1f;
+        let man = f64::from(h & 0x3ff);
+        let x = if exp == 0 {
+            man / 16_777_216.
+        } else {
+            (1024. + man) * f64::from(1_u32 << exp) / 33_554_432.
+        };
+        if h & 0x8000 != 0 { -x } else { x }
+    }
+
+    #[test]
+    fn to_f32_all() {
+        for h in 0..0x1_0000_u32 {
+            let h = h as u16;
+            let x = f16_to_f32(h);
+            let sign = u32::from(h & 0x8000) << 16;
+            if h & 0x7c00 != 0x7c00 {
+                assert_eq!(bits(x), bits(value(h) as f32), "{:#x}", h);
+            } else if h & 0x3ff == 0 {
+                assert_eq!(bits(x), sign | 0x7f80_0000);
+            } else {
+                let payload = u32::from(h & 0x3ff) << 13;
+                assert_eq!(bits(x), sign | 0x7fc0_0000 | payload);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_all() {
+        for h in 0..0x1_0000_u32 {
+            let h = h as u16;
+            // NaNs come back quiet.
+            let e = if h & 0x7c00 == 0x7c00 && h & 0x3ff != 0 {
+                h | 0x200
+            } else {
+                h
+            };
+            for &mode in MODES.iter() {
+                assert_eq!(f32_to_f16(f16_to_f32(h), mode), e, "{:#x}", h);
+            }
+        }
+    }
+
+    #[test]
+    fn from_f32_midpoints() {
+        // Every midpoint between two adjacent finite halves is exact in
+        // `f32`.
+        for h in 0..0x7bff_u16 {
+            let m = ((value(h) + value(h + 1)) / 2.) as f32;
+            let even = if h & 1 == 0 { h } else { h + 1 };
+            assert_eq!(f32_to_f16(m, RoundingMode::Nearest), even);
+            assert_eq!(f32_to_f16(m, RoundingMode::Down), h);
+            assert_eq!(f32_to_f16(m, RoundingMode::Up), h + 1);
+            assert_eq!(f32_to_f16(m, RoundingMode::TowardZero), h);
+
+            let (h, even) = (h | 0x8000, even | 0x8000);
+            assert_eq!(f32_to_f16(-m, RoundingMode::Nearest), even);
+            assert_eq!(f32_to_f16(-m, RoundingMode::Down), h + 1);
+            assert_eq!(f32_to_f16(-m, RoundingMode::Up), h);
+            assert_eq!(f32_to_f16(-m, RoundingMode::TowardZero), h);
+        }
+    }
+
+    #[test]
+    fn from_f32_out_of_range() {
+        use self::RoundingMode::*;
+        let cases: [(f32, [u16; 4]); 8] = [
+            (65519.99, [0x7bff, 0x7bff, 0x7c00, 0x7bff]),
+            (65520., [0x7c00, 0x7bff, 0x7c00, 0x7bff]),
+            (1e9, [0x7c00, 0x7bff, 0x7c00, 0x7bff]),
+            (-1e9, [0xfc00, 0xfc00, 0xfbff, 0xfbff]),
+            (1e-30, [0x0000, 0x0000, 0x0001, 0x0000]),
+            (-1e-30, [0x8000, 0x8001, 0x8000, 0x8000]),
+            (1e-45, [0x0000, 0x0000, 0x0001, 0x0000]),
+            (4e-8, [0x0001, 0x0000, 0x0001, 0x0000]),
+        ];
+        for &(x, e) in cases.iter() {
+            let modes = [Nearest, Down, Up, TowardZero];
+            for (&mode, &e) in modes.iter().zip(e.iter()) {
+                assert_eq!(f32_to_f16(x, mode), e, "{} {:?}", x, mode);
+            }
+        }
+    }
+}
@@ -33,12 +33,30 @@ extern crate stdsimd_test;
 #[cfg(test)]
 extern crate test;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 /// Platform independent SIMD vector types and operations.
 pub mod simd {
     pub use v128::*;
+    pub use v16::*;
     pub use v256::*;
+    pub use v32::*;
     pub use v512::*;
     pub use v64::*;
+    pub use vptr::*;
+
+    pub use f16::RoundingMode;
+
+    /// Small fixed-size linear algebra on `f32x4`.
+    pub mod linalg {
+        pub use linalg::{Mat4, Quat, Vec4};
+    }
 }
 
 /// Platform dependent vendor intrinsics.
@@ -61,7 +79,21 @@ pub mod vendor {
         // x86/x86_64:
         any(target_arch = "x86", target_arch = "x86_64")
     )]
-    pub use runtime::{__unstable_detect_feature, __Feature};
+    pub use runtime::{__unstable_detect_all, __unstable_detect_any,
+                      __unstable_detect_feature, __unstable_set_disable_hook,
+                      __Feature};
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub use runtime::{detected_features, is_detected, override_features,
+                      DetectedFeatures, Feature, ParseFeatureError};
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub use runtime::{CacheDescriptor, CacheInfo, CacheType, CpuInfo,
+                      CpuVendor, Topology, TopologyLevel, TopologyLevelType};
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub use runtime::{microarch_level, MicroarchLevel,
+                      __unstable_detect_level};
 }
 
 #[cfg(
@@ -74,44 +106,17 @@ mod runtime;
 #[macro_use]
 mod macros;
 mod simd_llvm;
+mod complex;
+mod compress;
+mod f16;
+mod linalg;
 mod v128;
+mod v16;
 mod v256;
+mod v32;
 mod v512;
 mod v64;
-
-/// 32-bit wide vector tpyes
-mod v32 {
-    use simd_llvm::*;
-
-    define_ty! { i16x2, i16, i16 }
-    define_impl! { i16x2, i16, 2, i16x2, x0, x1 }
-    define_ty! { u16x2, u16, u16 }
-    define_impl! { u16x2, u16, 2, i16x2, x0, x1 }
-
-    define_ty! { i8x4, i8, i8, i8, i8 }
-    define_impl! { i8x4, i8, 4, i8x4, x0, x1, x2, x3 }
-    define_ty! { u8x4, u8, u8, u8, u8 }
-    define_impl! { u8x4, u8, 4, i8x4, x0, x1, x2, x3 }
-
-    define_casts!(
-        (i16x2, i64x2, as_i64x2),
-        (u16x2, i64x2, as_i64x2),
-        (i8x4, i32x4, as_i32x4),
-        (u8x4, i32x4, as_i32x4)
-    );
-}
-
-/// 16-bit wide vector tpyes
-mod v16 {
-    use simd_llvm::*;
-
-    define_ty! { i8x2, i8, i8 }
-    define_impl! { i8x2, i8, 2, i8x2, x0, x1 }
-    define_ty! { u8x2, u8, u8 }
-    define_impl! { u8x2, u8, 2, i8x2, x0, x1 }
-
-    define_casts!((i8x2, i64x2, as_i64x2), (u8x2, i64x2, as_i64x2));
-}
+mod vptr;
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 mod x86;
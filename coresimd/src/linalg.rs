@@ -0,0 +1,562 @@
+//! Small fixed-size linear algebra on `f32x4`
+//!
+//! `Vec4` and `Quat` wrap a single `f32x4` (`x, y, z, w` in lanes 0 to 3).
+//! `Mat4` is a row-major 4x4 matrix stored as four `f32x4` rows and acts on
+//! column vectors: `m * v` computes `dot(m.rows[i], v)` in lane `i`.
+//!
+//! Dot products use `dpps` when SSE4.1 is enabled at compile-time, and the
+//! multiply-add chains use fused multiply-add when FMA (x86) or NEON
+//! (AArch64) is.
+
+use core::ops::{Add, Mul, Sub};
+
+use v128::f32x4;
+
+#[cfg(any(all(any(target_arch = "x86", target_arch = "x86_64"),
+              target_feature = "fma"),
+          all(target_arch = "aarch64", target_feature = "neon")))]
+#[allow(improper_ctypes)]
+extern "C" {
+    #[link_name = "llvm.fma.v4f32"]
+    fn fma_v4f32(a: f32x4, b: f32x4, c: f32x4) -> f32x4;
+}
+
+/// Computes `a * b + c`, fused where the target supports it.
+#[inline(always)]
+fn mul_add(a: f32x4, b: f32x4, c: f32x4) -> f32x4 {
+    #[cfg(any(all(any(target_arch = "x86", target_arch = "x86_64"),
+                  target_feature = "fma"),
+              all(target_arch = "aarch64", target_feature = "neon")))]
+    {
+        unsafe { fma_v4f32(a, b, c) }
+    }
+    #[cfg(not(any(all(any(target_arch = "x86", target_arch = "x86_64"),
+                      target_feature = "fma"),
+                  all(target_arch = "aarch64", target_feature = "neon"))))]
+    {
+        a * b + c
+    }
+}
+
+/// Sum of the lane-wise products of `a` and `b`.
+#[inline(always)]
+fn dot4(a: f32x4, b: f32x4) -> f32 {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+              target_feature = "sse4.1"))]
+    {
+        unsafe { ::vendor::_mm_dp_ps(a, b, 0xf1).extract(0) }
+    }
+    #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"),
+                  target_feature = "sse4.1")))]
+    {
+        use simd_llvm::simd_shuffle4;
+        let m = a * b;
+        let s: f32x4 = unsafe { simd_shuffle4(m, m, [2, 3, 0, 1]) };
+        let m = m + s;
+        let s: f32x4 = unsafe { simd_shuffle4(m, m, [1, 0, 3, 2]) };
+        (m + s).extract(0)
+    }
+}
+
+/// Cross product of the `x, y, z` lanes; lane `w` of the result is zero.
+#[inline(always)]
+fn cross3(a: f32x4, b: f32x4) -> f32x4 {
+    use simd_llvm::simd_shuffle4;
+    unsafe {
+        let a_yzx: f32x4 = simd_shuffle4(a, a, [1, 2, 0, 3]);
+        let b_yzx: f32x4 = simd_shuffle4(b, b, [1, 2, 0, 3]);
+        // a * b.yzx - a.yzx * b is the cross product in `y, z, x` order.
+        let c = a * b_yzx - a_yzx * b;
+        simd_shuffle4(c, c, [1, 2, 0, 3])
+    }
+}
+
+#[inline(always)]
+fn sqrt(x: f32) -> f32 {
+    unsafe { ::core::intrinsics::sqrtf32(x) }
+}
+
+/// A four-component vector.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec4(pub f32x4);
+
+impl Vec4 {
+    #[inline(always)]
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Vec4 {
+        Vec4(f32x4::new(x, y, z, w))
+    }
+
+    /// Dot product of all four components.
+    #[inline(always)]
+    pub fn dot(self, other: Vec4) -> f32 {
+        dot4(self.0, other.0)
+    }
+
+    /// Cross product of the `x, y, z` components. The `w` component of
+    /// the result is zero.
+    #[inline(always)]
+    pub fn cross(self, other: Vec4) -> Vec4 {
+        Vec4(cross3(self.0, other.0))
+    }
+
+    /// Euclidean length of all four components.
+    #[inline(always)]
+    pub fn length(self) -> f32 {
+        sqrt(self.dot(self))
+    }
+
+    /// Scales the vector to unit length. The result is not finite for the
+    /// zero vector.
+    #[inline(always)]
+    pub fn normalize(self) -> Vec4 {
+        self * (1. / self.length())
+    }
+}
+
+impl Add for Vec4 {
+    type Output = Vec4;
+    #[inline(always)]
+    fn add(self, other: Vec4) -> Vec4 {
+        Vec4(self.0 + other.0)
+    }
+}
+
+impl Sub for Vec4 {
+    type Output = Vec4;
+    #[inline(always)]
+    fn sub(self, other: Vec4) -> Vec4 {
+        Vec4(self.0 - other.0)
+    }
+}
+
+impl Mul<f32> for Vec4 {
+    type Output = Vec4;
+    #[inline(always)]
+    fn mul(self, s: f32) -> Vec4 {
+        Vec4(self.0 * f32x4::splat(s))
+    }
+}
+
+/// A row-major 4x4 matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4 {
+    pub rows: [f32x4; 4],
+}
+
+impl Mat4 {
+    #[inline(always)]
+    pub fn new(rows: [f32x4; 4]) -> Mat4 {
+        Mat4 { rows: rows }
+    }
+
+    #[inline(always)]
+    pub fn identity() -> Mat4 {
+        Mat4::new([
+            f32x4::new(1., 0., 0., 0.),
+            f32x4::new(0., 1., 0., 0.),
+            f32x4::new(0., 0., 1., 0.),
+            f32x4::new(0., 0., 0., 1.),
+        ])
+    }
+
+    #[inline(always)]
+    pub fn transpose(self) -> Mat4 {
+        Mat4::new(f32x4::transpose4x4(self.rows))
+    }
+
+    /// The 2x2 minors of the top two and bottom two rows, in the order
+    /// used by `determinant` and `inverse`.
+    #[inline(always)]
+    fn minors(&self) -> ([f32; 6], [f32; 6]) {
+        let a = |i: usize, j: u32| self.rows[i].extract(j);
+        let s = [
+            a(0, 0) * a(1, 1) - a(1, 0) * a(0, 1),
+            a(0, 0) * a(1, 2) - a(1, 0) * a(0, 2),
+            a(0, 0) * a(1, 3) - a(1, 0) * a(0, 3),
+            a(0, 1) * a(1, 2) - a(1, 1) * a(0, 2),
+            a(0, 1) * a(1, 3) - a(1, 1) * a(0, 3),
+            a(0, 2) * a(1, 3) - a(1, 2) * a(0, 3),
+        ];
+        let c = [
+            a(2, 0) * a(3, 1) - a(3, 0) * a(2, 1),
+            a(2, 0) * a(3, 2) - a(3, 0) * a(2, 2),
+            a(2, 0) * a(3, 3) - a(3, 0) * a(2, 3),
+            a(2, 1) * a(3, 2) - a(3, 1) * a(2, 2),
+            a(2, 1) * a(3, 3) - a(3, 1) * a(2, 3),
+            a(2, 2) * a(3, 3) - a(3, 2) * a(2, 3),
+        ];
+        (s, c)
+    }
+
+    #[inline]
+    pub fn determinant(self) -> f32 {
+        let (s, c) = self.minors();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1]
+            + s[5] * c[0]
+    }
+
+    /// Inverts the matrix, or returns `None` if it is singular.
+    #[inline]
+    pub fn inverse(self) -> Option<Mat4> {
+        let (s, c) = self.minors();
+        let det = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2]
+            - s[4] * c[1] + s[5] * c[0];
+        if det == 0. {
+            return None;
+        }
+        let a = |i: usize, j: u32| self.rows[i].extract(j);
+        let adj = [
+            f32x4::new(
+                a(1, 1) * c[5] - a(1, 2) * c[4] + a(1, 3) * c[3],
+                -a(0, 1) * c[5] + a(0, 2) * c[4] - a(0, 3) * c[3],
+                a(3, 1) * s[5] - a(3, 2) * s[4] + a(3, 3) * s[3],
+                -a(2, 1) * s[5] + a(2, 2) * s[4] - a(2, 3) * s[3],
+            ),
+            f32x4::new(
+                -a(1, 0) * c[5] + a(1, 2) * c[2] - a(1, 3) * c[1],
+                a(0, 0) * c[5] - a(0, 2) * c[2] + a(0, 3) * c[1],
+                -a(3, 0) * s[5] + a(3, 2) * s[2] - a(3, 3) * s[1],
+                a(2, 0) * s[5] - a(2, 2) * s[2] + a(2, 3) * s[1],
+            ),
+            f32x4::new(
+                a(1, 0) * c[4] - a(1, 1) * c[2] + a(1, 3) * c[0],
+                -a(0, 0) * c[4] + a(0, 1) * c[2] - a(0, 3) * c[0],
+                a(3, 0) * s[4] - a(3, 1) * s[2] + a(3, 3) * s[0],
+                -a(2, 0) * s[4] + a(2, 1) * s[2] - a(2, 3) * s[0],
+            ),
+            f32x4::new(
+                -a(1, 0) * c[3] + a(1, 1) * c[1] - a(1, 2) * c[0],
+                a(0, 0) * c[3] - a(0, 1) * c[1] + a(0, 2) * c[0],
+                -a(3, 0) * s[3] + a(3, 1) * s[1] - a(3, 2) * s[0],
+                a(2, 0) * s[3] - a(2, 1) * s[1] + a(2, 2) * s[0],
+            ),
+        ];
+        let r = f32x4::splat(1. / det);
+        Some(Mat4::new([adj[0] * r, adj[1] * r, adj[2] * r, adj[3] * r]))
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+    #[inline]
+    fn mul(self, other: Mat4) -> Mat4 {
+        let b = &other.rows;
+        let row = |a: f32x4| {
+            let r = f32x4::splat(a.extract(0)) * b[0];
+            let r = mul_add(f32x4::splat(a.extract(1)), b[1], r);
+            let r = mul_add(f32x4::splat(a.extract(2)), b[2], r);
+            mul_add(f32x4::splat(a.extract(3)), b[3], r)
+        };
+        let a = &self.rows;
+        Mat4::new([row(a[0]), row(a[1]), row(a[2]), row(a[3])])
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+    #[inline]
+    fn mul(self, v: Vec4) -> Vec4 {
+        let (a, v) = (&self.rows, v.0);
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                  target_feature = "sse4.1"))]
+        {
+            // Each `dpps` writes its dot product to one lane and zeroes
+            // the others.
+            use vendor::_mm_dp_ps;
+            unsafe {
+                Vec4(
+                    _mm_dp_ps(a[0], v, 0xf1) + _mm_dp_ps(a[1], v, 0xf2)
+                        + _mm_dp_ps(a[2], v, 0xf4)
+                        + _mm_dp_ps(a[3], v, 0xf8),
+                )
+            }
+        }
+        #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"),
+                      target_feature = "sse4.1")))]
+        {
+            let c = f32x4::transpose4x4(*a);
+            let r = c[0] * f32x4::splat(v.extract(0));
+            let r = mul_add(c[1], f32x4::splat(v.extract(1)), r);
+            let r = mul_add(c[2], f32x4::splat(v.extract(2)), r);
+            Vec4(mul_add(c[3], f32x4::splat(v.extract(3)), r))
+        }
+    }
+}
+
+/// A quaternion `x i + y j + z k + w`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat(pub f32x4);
+
+impl Quat {
+    #[inline(always)]
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Quat {
+        Quat(f32x4::new(x, y, z, w))
+    }
+
+    #[inline(always)]
+    pub fn identity() -> Quat {
+        Quat::new(0., 0., 0., 1.)
+    }
+
+    #[inline(always)]
+    pub fn dot(self, other: Quat) -> f32 {
+        dot4(self.0, other.0)
+    }
+
+    #[inline(always)]
+    pub fn length(self) -> f32 {
+        sqrt(self.dot(self))
+    }
+
+    /// Scales the quaternion to unit length. The result is not finite for
+    /// the zero quaternion.
+    #[inline(always)]
+    pub fn normalize(self) -> Quat {
+        Quat(self.0 * f32x4::splat(1. / self.length()))
+    }
+
+    #[inline(always)]
+    pub fn conjugate(self) -> Quat {
+        Quat(self.0 * f32x4::new(-1., -1., -1., 1.))
+    }
+
+    /// Rotates the `x, y, z` components of `v` by this unit quaternion.
+    /// The `w` component is kept.
+    #[inline]
+    pub fn rotate(self, v: Vec4) -> Vec4 {
+        // v + w t + u x t, with u = (x, y, z) and t = 2 u x v.
+        let u = self.0.replace(3, 0.);
+        let t = cross3(u, v.0) * f32x4::splat(2.);
+        let r = mul_add(f32x4::splat(self.0.extract(3)), t, v.0);
+        Vec4(r + cross3(u, t))
+    }
+
+    /// The rotation matrix of this unit quaternion.
+    #[inline]
+    pub fn to_mat4(self) -> Mat4 {
+        let (x, y, z, w) = (
+            self.0.extract(0),
+            self.0.extract(1),
+            self.0.extract(2),
+            self.0.extract(3),
+        );
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (xw, yw, zw) = (x * w, y * w, z * w);
+        Mat4::new([
+            f32x4::new(
+                1. - 2. * (yy + zz),
+                2. * (xy - zw),
+                2. * (xz + yw),
+                0.,
+            ),
+            f32x4::new(
+                2. * (xy + zw),
+                1. - 2. * (xx + zz),
+                2. * (yz - xw),
+                0.,
+            ),
+            f32x4::new(
+                2. * (xz - yw),
+                2. * (yz + xw),
+                1. - 2. * (xx + yy),
+                0.,
+            ),
+            f32x4::new(0., 0., 0., 1.),
+        ])
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+    /// The Hamilton product.
+    #[inline]
+    fn mul(self, other: Quat) -> Quat {
+        use simd_llvm::simd_shuffle4;
+        let (a, b) = (self.0, other.0);
+        let lane = |i| f32x4::splat(a.extract(i));
+        unsafe {
+            let wzyx: f32x4 = simd_shuffle4(b, b, [3, 2, 1, 0]);
+            let zwxy: f32x4 = simd_shuffle4(b, b, [2, 3, 0, 1]);
+            let yxwz: f32x4 = simd_shuffle4(b, b, [1, 0, 3, 2]);
+            let r = lane(3) * b;
+            let r = mul_add(lane(0), wzyx * f32x4::new(1., -1., 1., -1.), r);
+            let r = mul_add(lane(1), zwxy * f32x4::new(1., 1., -1., -1.), r);
+            Quat(mul_add(lane(2), yxwz * f32x4::new(-1., 1., 1., -1.), r))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M = [[f32; 4]; 4];
+
+    fn abs(x: f32) -> f32 {
+        if x < 0. { -x } else { x }
+    }
+
+    fn close(a: f32, b: f32) -> bool {
+        let scale = if abs(a) > abs(b) { abs(a) } else { abs(b) };
+        let scale = if scale > 1. { scale } else { 1. };
+        abs(a - b) <= 1e-4 * scale
+    }
+
+    fn assert_vec(v: f32x4, e: [f32; 4]) {
+        for (i, &e) in e.iter().enumerate() {
+            let x = v.extract(i as u32);
+            assert!(close(x, e), "{:?} != {:?}", v, e);
+        }
+    }
+
+    fn assert_mat(m: Mat4, e: M) {
+        for (r, e) in m.rows.iter().zip(e.iter()) {
+            assert_vec(*r, *e);
+        }
+    }
+
+    /// Deterministic values in `[-1, 1)`.
+    fn values(seed: &mut u32) -> [f32; 4] {
+        let mut r = [0.; 4];
+        for x in r.iter_mut() {
+            *seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            *x = (*seed >> 8) as f32 / 8_388_608. - 1.;
+        }
+        r
+    }
+
+    fn mat(m: M) -> Mat4 {
+        let row = |r: [f32; 4]| f32x4::new(r[0], r[1], r[2], r[3]);
+        Mat4::new([row(m[0]), row(m[1]), row(m[2]), row(m[3])])
+    }
+
+    fn scalar_mul(a: M, b: M) -> M {
+        let mut r = [[0.; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                for k in 0..4 {
+                    r[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn vec4() {
+        let mut seed = 1;
+        for _ in 0..100 {
+            let (a, b) = (values(&mut seed), values(&mut seed));
+            let va = Vec4(f32x4::load(&a, 0));
+            let vb = Vec4(f32x4::load(&b, 0));
+
+            let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3];
+            assert!(close(va.dot(vb), dot));
+
+            let cross = [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+                0.,
+            ];
+            assert_vec(va.cross(vb).0, cross);
+
+            let len =
+                sqrt(a[0] * a[0] + a[1] * a[1] + a[2] * a[2] + a[3] * a[3]);
+            assert!(close(va.length(), len));
+            let n = [a[0] / len, a[1] / len, a[2] / len, a[3] / len];
+            assert_vec(va.normalize().0, n);
+        }
+    }
+
+    #[test]
+    fn mat4() {
+        let mut seed = 2;
+        for _ in 0..100 {
+            let mut a = [[0.; 4]; 4];
+            let mut b = [[0.; 4]; 4];
+            for i in 0..4 {
+                a[i] = values(&mut seed);
+                b[i] = values(&mut seed);
+            }
+            let v = values(&mut seed);
+            let (ma, mb) = (mat(a), mat(b));
+
+            assert_mat(ma * mb, scalar_mul(a, b));
+
+            let mut t = [[0.; 4]; 4];
+            for i in 0..4 {
+                for j in 0..4 {
+                    t[j][i] = a[i][j];
+                }
+            }
+            assert_mat(ma.transpose(), t);
+
+            let mut mv = [0.; 4];
+            for i in 0..4 {
+                for j in 0..4 {
+                    mv[i] += a[i][j] * v[j];
+                }
+            }
+            assert_vec((ma * Vec4(f32x4::load(&v, 0))).0, mv);
+
+            // Skip nearly singular matrices, whose inverses are too large
+            // for a `1e-4` tolerance.
+            if abs(ma.determinant()) > 0.1 {
+                let inv = ma.inverse().unwrap();
+                let id = [
+                    [1., 0., 0., 0.],
+                    [0., 1., 0., 0.],
+                    [0., 0., 1., 0.],
+                    [0., 0., 0., 1.],
+                ];
+                assert_mat(ma * inv, id);
+                assert_mat(inv * ma, id);
+            }
+        }
+        assert_eq!(Mat4::identity().inverse(), Some(Mat4::identity()));
+        let singular =
+            mat([[1., 2., 3., 4.], [2., 4., 6., 8.], [0.; 4], [1.; 4]]);
+        assert_eq!(singular.inverse(), None);
+    }
+
+    #[test]
+    fn quat() {
+        let mut seed = 3;
+        for _ in 0..100 {
+            let (a, b) = (values(&mut seed), values(&mut seed));
+            let qa = Quat(f32x4::load(&a, 0));
+            let qb = Quat(f32x4::load(&b, 0));
+            let (x1, y1, z1, w1) = (a[0], a[1], a[2], a[3]);
+            let (x2, y2, z2, w2) = (b[0], b[1], b[2], b[3]);
+            let e = [
+                w1 * x2 + x1 * w2 + y1 * z2 - z1 * y2,
+                w1 * y2 - x1 * z2 + y1 * w2 + z1 * x2,
+                w1 * z2 + x1 * y2 - y1 * x2 + z1 * w2,
+                w1 * w2 - x1 * x2 - y1 * y2 - z1 * z2,
+            ];
+            assert_vec((qa * qb).0, e);
+            assert_vec(qa.conjugate().0, [-x1, -y1, -z1, w1]);
+
+            // Rotating with the quaternion and with its matrix agree, and
+            // match `q v q*`.
+            let q = qa.normalize();
+            assert!(close(q.length(), 1.));
+            let v = Vec4::new(b[0], b[1], b[2], 1.);
+            let r = q.rotate(v);
+            let p = q * Quat(v.0.replace(3, 0.)) * q.conjugate();
+            let e = [p.0.extract(0), p.0.extract(1), p.0.extract(2), 1.];
+            assert_vec(r.0, e);
+            assert_vec((q.to_mat4() * v).0, [
+                r.0.extract(0),
+                r.0.extract(1),
+                r.0.extract(2),
+                1.,
+            ]);
+        }
+        let v = Vec4::new(1., 2., 3., 4.);
+        assert_eq!(Quat::identity().rotate(v), v);
+        assert_eq!(Quat::identity().to_mat4(), Mat4::identity());
+    }
+}
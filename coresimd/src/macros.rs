@@ -159,6 +159,262 @@ macro_rules! define_from {
     }
 }
 
+macro_rules! define_array_traits {
+    ($(($ty:ident, $elem:ident, $nelems:expr)),+) => {
+        $(
+            impl $ty {
+                /// Views the lanes as an array.
+                #[inline(always)]
+                pub fn as_array(&self) -> &[$elem; $nelems] {
+                    unsafe {
+                        &*(self as *const $ty as *const [$elem; $nelems])
+                    }
+                }
+
+                /// Views the lanes as a mutable array.
+                #[inline(always)]
+                pub fn as_mut_array(&mut self) -> &mut [$elem; $nelems] {
+                    unsafe {
+                        &mut *(self as *mut $ty as *mut [$elem; $nelems])
+                    }
+                }
+            }
+
+            impl Default for $ty {
+                #[inline(always)]
+                fn default() -> Self {
+                    $ty::splat(0 as $elem)
+                }
+            }
+
+            impl From<[$elem; $nelems]> for $ty {
+                #[inline(always)]
+                fn from(a: [$elem; $nelems]) -> $ty {
+                    unsafe { ::core::mem::transmute(a) }
+                }
+            }
+
+            impl From<$ty> for [$elem; $nelems] {
+                #[inline(always)]
+                fn from(v: $ty) -> [$elem; $nelems] {
+                    unsafe { ::core::mem::transmute(v) }
+                }
+            }
+
+            impl<'a> IntoIterator for &'a $ty {
+                type Item = &'a $elem;
+                type IntoIter = ::core::slice::Iter<'a, $elem>;
+                #[inline(always)]
+                fn into_iter(self) -> Self::IntoIter {
+                    self.as_array().iter()
+                }
+            }
+
+            impl<'a> IntoIterator for &'a mut $ty {
+                type Item = &'a mut $elem;
+                type IntoIter = ::core::slice::IterMut<'a, $elem>;
+                #[inline(always)]
+                fn into_iter(self) -> Self::IntoIter {
+                    self.as_mut_array().iter_mut()
+                }
+            }
+
+            /// Collects exactly as many elements as there are lanes.
+            ///
+            /// # Panics
+            ///
+            /// If the iterator yields fewer or more elements.
+            impl ::core::iter::FromIterator<$elem> for $ty {
+                fn from_iter<I>(iter: I) -> $ty
+                where
+                    I: IntoIterator<Item = $elem>,
+                {
+                    let mut r = $ty::splat(0 as $elem);
+                    let mut iter = iter.into_iter();
+                    for i in 0..$nelems {
+                        match iter.next() {
+                            Some(x) => r = r.replace(i, x),
+                            None => panic!(
+                                "{} needs {} elements, got {}",
+                                stringify!($ty), $nelems, i
+                            ),
+                        }
+                    }
+                    assert!(
+                        iter.next().is_none(),
+                        "{} needs {} elements, got more",
+                        stringify!($ty), $nelems
+                    );
+                    r
+                }
+            }
+
+            impl ::core::iter::Sum for $ty {
+                #[inline]
+                fn sum<I: Iterator<Item = $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::splat(0 as $elem), |a, b| unsafe {
+                        simd_add(a, b)
+                    })
+                }
+            }
+
+            impl<'a> ::core::iter::Sum<&'a $ty> for $ty {
+                #[inline]
+                fn sum<I: Iterator<Item = &'a $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::splat(0 as $elem), |a, &b| unsafe {
+                        simd_add(a, b)
+                    })
+                }
+            }
+
+            impl ::core::iter::Product for $ty {
+                #[inline]
+                fn product<I: Iterator<Item = $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::splat(1 as $elem), |a, b| unsafe {
+                        simd_mul(a, b)
+                    })
+                }
+            }
+
+            impl<'a> ::core::iter::Product<&'a $ty> for $ty {
+                #[inline]
+                fn product<I: Iterator<Item = &'a $ty>>(iter: I) -> $ty {
+                    iter.fold($ty::splat(1 as $elem), |a, &b| unsafe {
+                        simd_mul(a, b)
+                    })
+                }
+            }
+        )+
+    }
+}
+
+/// Serializes vectors as a tuple (a fixed-length sequence) of their lanes.
+/// Deserializing a sequence of the wrong length is an error.
+macro_rules! define_serde {
+    ($(($ty:ident, $elem:ident, $nelems:expr)),+) => {
+        $(
+            #[cfg(feature = "serde")]
+            impl ::serde::Serialize for $ty {
+                fn serialize<S>(
+                    &self, serializer: S
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    use serde::ser::SerializeTuple;
+                    let mut t = serializer.serialize_tuple($nelems)?;
+                    for x in self.as_array().iter() {
+                        t.serialize_element(x)?;
+                    }
+                    t.end()
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de> ::serde::Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<$ty, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    use core::fmt;
+                    use serde::de::{Error, SeqAccess, Visitor};
+
+                    struct LaneVisitor;
+
+                    impl<'de> Visitor<'de> for LaneVisitor {
+                        type Value = $ty;
+
+                        fn expecting(
+                            &self, f: &mut fmt::Formatter
+                        ) -> fmt::Result {
+                            write!(
+                                f, "a sequence of {} {} values",
+                                $nelems, stringify!($elem)
+                            )
+                        }
+
+                        fn visit_seq<A>(
+                            self, mut seq: A
+                        ) -> Result<$ty, A::Error>
+                        where
+                            A: SeqAccess<'de>,
+                        {
+                            let mut r = $ty::splat(0 as $elem);
+                            for i in 0..$nelems {
+                                match seq.next_element()? {
+                                    Some(x) => r = r.replace(i, x),
+                                    None => {
+                                        return Err(Error::invalid_length(
+                                            i as usize, &self,
+                                        ))
+                                    }
+                                }
+                            }
+                            if seq.next_element::<$elem>()?.is_some() {
+                                return Err(Error::invalid_length(
+                                    $nelems + 1, &self,
+                                ));
+                            }
+                            Ok(r)
+                        }
+                    }
+
+                    deserializer.deserialize_tuple($nelems, LaneVisitor)
+                }
+            }
+        )+
+    }
+}
+
+/// Serializes vectors that are not plain lanes of a primitive type as
+/// their `$repr` vector: the bit patterns of the lanes of `f16` vectors and
+/// the interleaved real and imaginary parts of complex vectors.
+macro_rules! define_serde_via {
+    ($(($ty:ident, $repr:ident, $to:ident, $from:ident)),+) => {
+        $(
+            #[cfg(feature = "serde")]
+            impl ::serde::Serialize for $ty {
+                fn serialize<S>(
+                    &self, serializer: S
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    ::serde::Serialize::serialize(&self.$to(), serializer)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de> ::serde::Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<$ty, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    let r: ::simd::$repr =
+                        ::serde::Deserialize::deserialize(deserializer)?;
+                    Ok($ty::$from(r))
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! define_integer_traits {
+    ($($ty:ident),+) => {
+        $(
+            impl Eq for $ty {}
+
+            impl ::core::hash::Hash for $ty {
+                #[inline]
+                fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                    // Arrays only implement `Hash` up to 32 elements:
+                    self.as_array()[..].hash(state)
+                }
+            }
+        )+
+    }
+}
+
 macro_rules! define_common_ops {
     ($($ty:ident),+) => {
         $(
@@ -359,148 +615,1577 @@ macro_rules! define_integer_ops {
                 $ty, $elem,
                 u8, u16, u32, u64, usize,
                 i8, i16, i32, i64, isize);
+        )+
+    }
+}
 
-            impl ::core::fmt::LowerHex for $ty {
-                fn fmt(&self, f: &mut ::core::fmt::Formatter)
-                       -> ::core::fmt::Result {
-                    write!(f, "{}(", stringify!($ty))?;
-                    let n = ::core::mem::size_of_val(self)
-                        / ::core::mem::size_of::<$elem>();
-                    for i in 0..n {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "{:#x}", self.extract(i as u32))?;
+/// Byte shifts of the 128-bit-lane types. On vectors wider than 128 bits
+/// every lane is shifted on its own, like `vpslldq`/`vpsrldq`.
+macro_rules! define_byte_shifts {
+    ($(($ty:ident, $uty:ident)),+) => {
+        $(
+            impl $ty {
+                /// Shifts each 128-bit lane left by `n` bytes, shifting in
+                /// zeros. This is a numeric shift: on little-endian targets
+                /// it matches `_mm_slli_si128`. Shifting by 16 bytes or
+                /// more yields zero.
+                #[inline(always)]
+                pub fn shl_bytes(self, n: u32) -> $ty {
+                    if n >= 16 {
+                        $ty::splat(0)
+                    } else {
+                        self << (8 * n)
+                    }
+                }
+
+                /// Shifts each 128-bit lane right by `n` bytes, shifting
+                /// in zeros (also for signed lanes). This is a numeric
+                /// shift: on little-endian targets it matches
+                /// `_mm_srli_si128`. Shifting by 16 bytes or more yields
+                /// zero.
+                #[inline(always)]
+                pub fn shr_bytes(self, n: u32) -> $ty {
+                    if n >= 16 {
+                        $ty::splat(0)
+                    } else {
+                        let x = ::simd::$uty::from(self) >> (8 * n);
+                        $ty::from(x)
                     }
-                    write!(f, ")")
                 }
             }
         )+
     }
 }
 
-macro_rules! define_casts {
-    ($(($fromty:ident, $toty:ident, $cast:ident)),+) => {
+/// Byte-order conversions of the integer vectors. `$bytes` is the `u8`
+/// vector of the same width and `$bswap` the matching `llvm.bswap`
+/// intrinsic, used when neither `pshufb` nor `vrev` applies.
+macro_rules! define_byte_order {
+    ($(($ty:ident, $bytes:ident, $bswap:tt
+        $(, x86($xf:tt): $pshufb:ident, [$($idx:expr),+])*
+        $(, arm($af:tt): $vrev:ident)*)),+) => {
         $(
-            impl $fromty {
+            impl $ty {
+                /// Reverses the order of the bytes of each lane.
                 #[inline(always)]
-                pub fn $cast(self) -> ::simd::$toty {
-                    unsafe { simd_cast(self) }
+                pub fn swap_bytes(self) -> $ty {
+                    $(
+                        #[cfg(all(any(target_arch = "x86",
+                                      target_arch = "x86_64"),
+                                  target_feature = $xf))]
+                        {
+                            let m = ::simd::$bytes::new($($idx),+);
+                            let b = ::simd::$bytes::from(self);
+                            $ty::from(unsafe { ::vendor::$pshufb(b, m) })
+                        }
+                    )*
+                    $(
+                        #[cfg(all(any(target_arch = "arm",
+                                      target_arch = "aarch64"),
+                                  target_feature = $af))]
+                        {
+                            let b = ::simd::$bytes::from(self);
+                            $ty::from(unsafe { ::vendor::$vrev(b) })
+                        }
+                    )*
+                    #[cfg(not(any(
+                        $(all(any(target_arch = "x86",
+                                  target_arch = "x86_64"),
+                              target_feature = $xf),)*
+                        $(all(any(target_arch = "arm",
+                                  target_arch = "aarch64"),
+                              target_feature = $af),)*
+                    )))]
+                    {
+                        #[allow(improper_ctypes)]
+                        extern "C" {
+                            #[link_name = $bswap]
+                            fn bswap(x: $ty) -> $ty;
+                        }
+                        unsafe { bswap(self) }
+                    }
+                }
+
+                /// Reads the lanes from big-endian bytes.
+                #[inline(always)]
+                pub fn from_be_bytes(bytes: ::simd::$bytes) -> $ty {
+                    let x = $ty::from(bytes);
+                    if cfg!(target_endian = "little") {
+                        x.swap_bytes()
+                    } else {
+                        x
+                    }
+                }
+
+                /// Reads the lanes from little-endian bytes.
+                #[inline(always)]
+                pub fn from_le_bytes(bytes: ::simd::$bytes) -> $ty {
+                    let x = $ty::from(bytes);
+                    if cfg!(target_endian = "little") {
+                        x
+                    } else {
+                        x.swap_bytes()
+                    }
+                }
+
+                /// Returns the big-endian bytes of the lanes.
+                #[inline(always)]
+                pub fn to_be_bytes(self) -> ::simd::$bytes {
+                    if cfg!(target_endian = "little") {
+                        ::simd::$bytes::from(self.swap_bytes())
+                    } else {
+                        ::simd::$bytes::from(self)
+                    }
+                }
+
+                /// Returns the little-endian bytes of the lanes.
+                #[inline(always)]
+                pub fn to_le_bytes(self) -> ::simd::$bytes {
+                    if cfg!(target_endian = "little") {
+                        ::simd::$bytes::from(self)
+                    } else {
+                        ::simd::$bytes::from(self.swap_bytes())
+                    }
+                }
+
+                /// Loads big-endian lanes from `slice[offset..]`.
+                ///
+                /// # Panics
+                ///
+                /// If `slice[offset..]` is shorter than the vector.
+                #[inline(always)]
+                pub fn load_be(slice: &[u8], offset: usize) -> $ty {
+                    $ty::from_be_bytes(::simd::$bytes::load(slice, offset))
+                }
+
+                /// Loads little-endian lanes from `slice[offset..]`.
+                ///
+                /// # Panics
+                ///
+                /// If `slice[offset..]` is shorter than the vector.
+                #[inline(always)]
+                pub fn load_le(slice: &[u8], offset: usize) -> $ty {
+                    $ty::from_le_bytes(::simd::$bytes::load(slice, offset))
                 }
             }
         )+
     }
 }
 
-#[cfg(test)]
-#[macro_export]
-macro_rules! test_arithmetic_ {
-        ($tn:ident, $zero:expr, $one:expr, $two:expr, $four:expr) => {
-            {
-                 let z = $tn::splat($zero);
-                 let o = $tn::splat($one);
-                 let t = $tn::splat($two);
-                 let f = $tn::splat($four);
+macro_rules! define_ptr {
+    (
+        $name:ident, $m:tt, $nelems:expr, $uty:ident, $ity:ident,
+        $elem:ident, $($idx:expr => $p:ident),+ | $(#[$doc:meta])*
+    ) => {
+        $(#[$doc])*
+        #[allow(non_camel_case_types)]
+        pub struct $name<T> {
+            addr: $uty,
+            _marker: ::core::marker::PhantomData<*$m T>,
+        }
 
-                 // add
-                 assert_eq!(z + z, z);
-                 assert_eq!(o + z, o);
-                 assert_eq!(t + z, t);
-                 assert_eq!(t + t, f);
-                 // sub
-                 assert_eq!(z - z, z);
-                 assert_eq!(o - z, o);
-                 assert_eq!(t - z, t);
-                 assert_eq!(f - t, t);
-                 assert_eq!(f - o - o, t);
-                 // mul
-                 assert_eq!(z * z, z);
-                 assert_eq!(z * o, z);
-                 assert_eq!(z * t, z);
-                 assert_eq!(o * t, t);
-                 assert_eq!(t * t, f);
-                 // div
-                 assert_eq!(z / o, z);
-                 assert_eq!(t / o, t);
-                 assert_eq!(f / o, f);
-                 assert_eq!(t / t, o);
-                 assert_eq!(f / t, t);
-                 // rem
-                 assert_eq!(o % o, z);
-                 assert_eq!(f % t, z);
+        impl<T> Clone for $name<T> {
+            #[inline(always)]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
 
-                {
-                    let mut v = z;
-                    assert_eq!(v, z);
-                    v += o;  // add_assign
-                    assert_eq!(v, o);
-                    v -= o; // sub_assign
-                    assert_eq!(v, z);
-                    v = t;
-                    v *= o; // mul_assign
-                    assert_eq!(v, t);
-                    v *= t;
-                    assert_eq!(v, f);
-                    v /= o; // div_assign
-                    assert_eq!(v, f);
-                    v /= t;
-                    assert_eq!(v, t);
-                    v %= t; // rem_assign
-                    assert_eq!(v, z);
-                }
+        impl<T> Copy for $name<T> {}
+
+        impl<T> PartialEq for $name<T> {
+            #[inline(always)]
+            fn eq(&self, other: &Self) -> bool {
+                self.addr == other.addr
             }
-        };
-    }
+        }
 
-#[cfg(test)]
-#[macro_export]
-macro_rules! test_neg_ {
-        ($tn:ident, $zero:expr, $one:expr, $two:expr, $four:expr) => {
-            {
-                let z = $tn::splat($zero);
-                let o = $tn::splat($one);
-                let t = $tn::splat($two);
-                let f = $tn::splat($four);
+        impl<T> Eq for $name<T> {}
 
-                let nz = $tn::splat(-$zero);
-                let no = $tn::splat(-$one);
-                let nt = $tn::splat(-$two);
-                let nf = $tn::splat(-$four);
+        impl<T> ::core::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter)
+                   -> ::core::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    $(.field(&self.extract($idx)))+
+                    .finish()
+            }
+        }
 
-                assert_eq!(-z, nz);
-                assert_eq!(-o, no);
-                assert_eq!(-t, nt);
-                assert_eq!(-f, nf);
+        impl<T> $name<T> {
+            #[inline(always)]
+            pub fn new($($p: *$m T),+) -> $name<T> {
+                $name::from_addr($uty::new($($p as usize as $elem),+))
             }
-        };
-    }
 
-#[cfg(test)]
-#[macro_export]
-macro_rules! test_bit_arithmetic_ {
-    ($tn:ident) => {
-        {
-            let z = $tn::splat(0);
-            let o = $tn::splat(1);
-            let t = $tn::splat(2);
-            let f = $tn::splat(4);
-            let m = $tn::splat(!z.extract(0));
+            #[inline(always)]
+            pub fn len() -> i32 {
+                $nelems
+            }
 
-            // shr
-            assert_eq!(o >> 1, z);
-            assert_eq!(t >> 1, o);
-            assert_eq!(f >> 1, t);
-            // shl
-            assert_eq!(o << 1, t);
-            assert_eq!(o << 2, f);
-            assert_eq!(t << 1, f);
-            // bitand
-            assert_eq!(o & o, o);
-            assert_eq!(t & t, t);
-            assert_eq!(t & o, z);
-            // bitor
-            assert_eq!(o | o, o);
-            assert_eq!(t | t, t);
+            #[inline(always)]
+            pub fn splat(p: *$m T) -> $name<T> {
+                $name::from_addr($uty::splat(p as usize as $elem))
+            }
+
+            /// Creates a pointer vector from the lane addresses.
+            #[inline(always)]
+            pub fn from_addr(addr: $uty) -> $name<T> {
+                $name {
+                    addr: addr,
+                    _marker: ::core::marker::PhantomData,
+                }
+            }
+
+            /// Returns the addresses of the lanes.
+            #[inline(always)]
+            pub fn addr(self) -> $uty {
+                self.addr
+            }
+
+            #[inline(always)]
+            pub fn extract(self, idx: u32) -> *$m T {
+                self.addr.extract(idx) as usize as *$m T
+            }
+
+            #[inline(always)]
+            pub fn replace(self, idx: u32, p: *$m T) -> $name<T> {
+                $name::from_addr(self.addr.replace(idx, p as usize as $elem))
+            }
+
+            /// Returns a mask of the lanes that are null.
+            #[inline(always)]
+            pub fn is_null(self) -> $ity {
+                self.addr.eq($uty::splat(0))
+            }
+
+            /// Casts every lane to a pointer to `U`.
+            #[inline(always)]
+            pub fn cast<U>(self) -> $name<U> {
+                $name::from_addr(self.addr)
+            }
+
+            /// Offsets every lane by the matching lane of `count`, in
+            /// units of `T`.
+            ///
+            /// # Safety
+            ///
+            /// Every lane must satisfy the requirements of the `offset`
+            /// method of raw pointers.
+            #[inline(always)]
+            pub unsafe fn offset(self, count: $ity) -> $name<T> {
+                self.wrapping_offset(count)
+            }
+
+            /// Offsets every lane by the matching lane of `count`, in
+            /// units of `T`, wrapping around the address space.
+            #[inline(always)]
+            pub fn wrapping_offset(self, count: $ity) -> $name<T> {
+                let count: $uty = unsafe { simd_cast(count) };
+                self.wrapping_add(count)
+            }
+
+            /// Adds the matching lane of `count`, in units of `T`, to
+            /// every lane, wrapping around the address space.
+            #[inline(always)]
+            pub fn wrapping_add(self, count: $uty) -> $name<T> {
+                let size = ::core::mem::size_of::<T>() as $elem;
+                unsafe {
+                    let bytes = simd_mul(count, $uty::splat(size));
+                    $name::from_addr(simd_add(self.addr, bytes))
+                }
+            }
+        }
+    }
+}
+
+macro_rules! define_mut_ptr {
+    ($(($name:ident, $cname:ident)),+) => {
+        $(
+            impl<T> $name<T> {
+                /// Converts the lanes to `*const T` pointers.
+                #[inline(always)]
+                pub fn as_const(self) -> $cname<T> {
+                    $cname::from_addr(self.addr())
+                }
+            }
+
+            impl<T> From<$name<T>> for $cname<T> {
+                #[inline(always)]
+                fn from(p: $name<T>) -> $cname<T> {
+                    p.as_const()
+                }
+            }
+        )+
+    }
+}
+
+/// `gather` and `scatter` between the pointer vectors to `$elem` and the
+/// vectors of `$elem` lanes, through LLVM's masked gather and scatter with
+/// every lane enabled. `$craw` and `$mraw` are the LLVM vectors of pointers
+/// with the layout of the lane addresses.
+macro_rules! define_gather {
+    ($cname:ident, $mname:ident, $craw:ident, $mraw:ident, $mask:ident:
+     $(($elem:ident, $vty:ident)),+) => {
+        $(
+            impl $cname<$elem> {
+                /// Reads the value every lane points to.
+                ///
+                /// # Safety
+                ///
+                /// Every lane must be valid for reads and properly aligned.
+                #[inline(always)]
+                pub unsafe fn gather(self) -> ::simd::$vty {
+                    let ptrs: $craw<$elem> =
+                        ::core::mem::transmute(self.addr());
+                    simd_gather(
+                        ::simd::$vty::splat(0 as $elem), ptrs,
+                        $mask::splat(-1),
+                    )
+                }
+            }
+
+            impl $mname<$elem> {
+                /// Writes lane `i` of `values` to the location lane `i`
+                /// points to, in increasing lane order: if several lanes
+                /// point to the same location, the highest lane wins.
+                ///
+                /// # Safety
+                ///
+                /// Every lane must be valid for writes and properly
+                /// aligned.
+                #[inline(always)]
+                pub unsafe fn scatter(self, values: ::simd::$vty) {
+                    let ptrs: $mraw<$elem> =
+                        ::core::mem::transmute(self.addr());
+                    simd_scatter(values, ptrs, $mask::splat(-1));
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! define_f16 {
+    ($(($ty:ident, $bits:ident, $fty:ident,
+        $widen:ident, $narrow:ident)),+) => {
+        $(
+            impl $ty {
+                /// Reinterprets binary16 bit patterns as half-precision
+                /// lanes.
+                #[inline(always)]
+                pub fn from_bits(bits: ::simd::$bits) -> $ty {
+                    unsafe { ::core::mem::transmute(bits) }
+                }
+
+                /// Returns the binary16 bit patterns of the lanes.
+                #[inline(always)]
+                pub fn to_bits(self) -> ::simd::$bits {
+                    unsafe { ::core::mem::transmute(self) }
+                }
+
+                /// Converts the lanes to `f32`. This conversion is exact.
+                #[inline]
+                pub fn to_f32(self) -> ::simd::$fty {
+                    ::f16::$widen(self.to_bits())
+                }
+
+                /// Converts `f32` lanes to half precision, rounding in
+                /// the direction of `mode`.
+                ///
+                /// Values beyond the largest finite half, 65504, become
+                /// infinities or stay at the largest finite half as `mode`
+                /// dictates. NaNs stay NaN and keep the upper bits of
+                /// their payload.
+                #[inline]
+                pub fn from_f32(
+                    x: ::simd::$fty, mode: ::simd::RoundingMode
+                ) -> $ty {
+                    $ty::from_bits(::f16::$narrow(x, mode))
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! define_complex {
+    ($(($ty:ident, $fty:ident, $mul:ident, $conj:ident, $mul_i:ident)),+) => {
+        $(
+            impl $ty {
+                /// Reinterprets interleaved `[re, im, re, im, ...]` lanes as
+                /// complex numbers.
+                #[inline(always)]
+                pub fn from_interleaved(v: ::simd::$fty) -> $ty {
+                    unsafe { ::core::mem::transmute(v) }
+                }
+
+                /// Returns the complex numbers as interleaved
+                /// `[re, im, re, im, ...]` lanes.
+                #[inline(always)]
+                pub fn to_interleaved(self) -> ::simd::$fty {
+                    unsafe { ::core::mem::transmute(self) }
+                }
+
+                /// Returns the complex conjugate of each lane.
+                #[inline(always)]
+                pub fn conj(self) -> $ty {
+                    $ty::from_interleaved(::complex::$conj(
+                        self.to_interleaved(),
+                    ))
+                }
+
+                /// Multiplies each lane by the imaginary unit `i`.
+                #[inline(always)]
+                pub fn mul_i(self) -> $ty {
+                    $ty::from_interleaved(::complex::$mul_i(
+                        self.to_interleaved(),
+                    ))
+                }
+            }
+
+            impl ::core::ops::Add for $ty {
+                type Output = $ty;
+                #[inline(always)]
+                fn add(self, other: $ty) -> $ty {
+                    $ty::from_interleaved(
+                        self.to_interleaved() + other.to_interleaved(),
+                    )
+                }
+            }
+
+            impl ::core::ops::Sub for $ty {
+                type Output = $ty;
+                #[inline(always)]
+                fn sub(self, other: $ty) -> $ty {
+                    $ty::from_interleaved(
+                        self.to_interleaved() - other.to_interleaved(),
+                    )
+                }
+            }
+
+            impl ::core::ops::Mul for $ty {
+                type Output = $ty;
+                #[inline(always)]
+                fn mul(self, other: $ty) -> $ty {
+                    $ty::from_interleaved(::complex::$mul(
+                        self.to_interleaved(),
+                        other.to_interleaved(),
+                    ))
+                }
+            }
+        )+
+    }
+}
+
+/// Formats the lanes as `[x0, x1, ...]`, applying the formatting flags
+/// (width, fill, precision, `#`, ...) to each lane.
+macro_rules! impl_lane_fmt {
+    ($ty:ident, $($trait:ident),+) => {
+        $(
+            impl ::core::fmt::$trait for $ty {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter)
+                       -> ::core::fmt::Result {
+                    f.write_str("[")?;
+                    for (i, x) in self.as_array().iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        ::core::fmt::$trait::fmt(x, f)?;
+                    }
+                    f.write_str("]")
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! define_float_fmt {
+    ($($ty:ident),+) => {
+        $(
+            impl_lane_fmt!($ty, Display);
+        )+
+    }
+}
+
+macro_rules! define_integer_fmt {
+    ($($ty:ident),+) => {
+        $(
+            impl_lane_fmt!($ty, Display, LowerHex, UpperHex, Octal, Binary);
+        )+
+    }
+}
+
+macro_rules! define_casts {
+    ($(($fromty:ident, $toty:ident, $cast:ident)),+) => {
+        $(
+            impl $fromty {
+                #[inline(always)]
+                pub fn $cast(self) -> ::simd::$toty {
+                    unsafe { simd_cast(self) }
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! define_abs_diff {
+    ($(($ty:ident, $uty:ident)),+) => {
+        $(
+            impl $ty {
+                /// Lane-wise absolute difference `|self - other|`.
+                ///
+                /// The result has unsigned lanes, so the difference of any
+                /// two lanes is representable without overflow.
+                #[inline(always)]
+                pub fn abs_diff(self, other: $ty) -> ::simd::$uty {
+                    use core::mem::transmute;
+                    unsafe {
+                        let m: ::simd::$uty = transmute(self.gt(other));
+                        let x: ::simd::$uty = transmute(self - other);
+                        let y: ::simd::$uty = transmute(other - self);
+                        (x & m) | (y & !m)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+/// Portable fallback of the sum of absolute differences: lane `i` of the
+/// result is the sum of the absolute differences of the bytes
+/// `8 * i..8 * i + 8`.
+macro_rules! portable_sad_u8 {
+    ($a:expr, $b:expr, $rty:ident) => {
+        {
+            let d = $a.abs_diff($b);
+            let mut r = ::simd::$rty::splat(0);
+            for i in 0..::simd::$rty::len() as u32 {
+                let mut s = 0_u64;
+                for j in 0..8 {
+                    s += u64::from(d.extract(8 * i + j));
+                }
+                r = r.replace(i, s);
+            }
+            r
+        }
+    }
+}
+
+macro_rules! define_sad_u8 {
+    (u8x16, u64x2) => {
+        impl u8x16 {
+            /// Sum of absolute differences.
+            ///
+            /// Lane `i` of the result is the sum of `|self - other|` over
+            /// the bytes `8 * i..8 * i + 8`. This is the layout produced by
+            /// `psadbw`, which is used when SSE2 is enabled; on NEON it
+            /// lowers to `uabd` followed by pairwise widening adds.
+            #[inline(always)]
+            pub fn sad_u8(self, other: u8x16) -> ::simd::u64x2 {
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                          target_feature = "sse2"))]
+                {
+                    unsafe { ::vendor::_mm_sad_epu8(self, other) }
+                }
+                #[cfg(all(any(target_arch = "arm", target_arch = "aarch64"),
+                          target_feature = "neon"))]
+                {
+                    use vendor::{vabdq_u8, vpaddlq_u16, vpaddlq_u32,
+                                 vpaddlq_u8};
+                    unsafe {
+                        let d = vabdq_u8(self, other);
+                        vpaddlq_u32(vpaddlq_u16(vpaddlq_u8(d)))
+                    }
+                }
+                #[cfg(not(any(
+                    all(any(target_arch = "x86", target_arch = "x86_64"),
+                        target_feature = "sse2"),
+                    all(any(target_arch = "arm", target_arch = "aarch64"),
+                        target_feature = "neon"))))]
+                {
+                    portable_sad_u8!(self, other, u64x2)
+                }
+            }
+        }
+    };
+    (u8x32, u64x4) => {
+        impl u8x32 {
+            /// Sum of absolute differences.
+            ///
+            /// Lane `i` of the result is the sum of `|self - other|` over
+            /// the bytes `8 * i..8 * i + 8`. This is the layout produced by
+            /// `vpsadbw`, which is used when AVX2 is enabled.
+            #[inline(always)]
+            pub fn sad_u8(self, other: u8x32) -> ::simd::u64x4 {
+                #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                          target_feature = "avx2"))]
+                {
+                    unsafe { ::vendor::_mm256_sad_epu8(self, other) }
+                }
+                #[cfg(not(all(any(target_arch = "x86",
+                                  target_arch = "x86_64"),
+                              target_feature = "avx2")))]
+                {
+                    portable_sad_u8!(self, other, u64x4)
+                }
+            }
+        }
+    };
+    ($ty:ident, $rty:ident) => {
+        impl $ty {
+            /// Sum of absolute differences.
+            ///
+            /// Lane `i` of the result is the sum of `|self - other|` over
+            /// the bytes `8 * i..8 * i + 8`.
+            #[inline(always)]
+            pub fn sad_u8(self, other: $ty) -> ::simd::$rty {
+                portable_sad_u8!(self, other, $rty)
+            }
+        }
+    };
+}
+
+macro_rules! define_avg {
+    ($(($ty:ident $(, x86($xf:tt): $pavg:ident)*
+                  $(, arm($af:tt): $rhadd:ident, $hadd:ident)*)),+) => {
+        $(
+            impl $ty {
+                /// Lane-wise average rounded up: `(self + other + 1) >> 1`.
+                ///
+                /// The intermediate sum does not overflow.
+                #[inline(always)]
+                pub fn avg_round(self, other: $ty) -> $ty {
+                    $(
+                        #[cfg(all(any(target_arch = "x86",
+                                      target_arch = "x86_64"),
+                                  target_feature = $xf))]
+                        {
+                            unsafe { ::vendor::$pavg(self, other) }
+                        }
+                    )*
+                    $(
+                        #[cfg(all(any(target_arch = "arm",
+                                      target_arch = "aarch64"),
+                                  target_feature = $af))]
+                        {
+                            unsafe { ::vendor::$rhadd(self, other) }
+                        }
+                    )*
+                    #[cfg(not(any(
+                        $(all(any(target_arch = "x86",
+                                  target_arch = "x86_64"),
+                              target_feature = $xf),)*
+                        $(all(any(target_arch = "arm",
+                                  target_arch = "aarch64"),
+                              target_feature = $af),)*
+                    )))]
+                    {
+                        (self | other) - ((self ^ other) >> 1)
+                    }
+                }
+
+                /// Lane-wise average rounded down: `(self + other) >> 1`.
+                ///
+                /// The intermediate sum does not overflow.
+                #[inline(always)]
+                pub fn avg_floor(self, other: $ty) -> $ty {
+                    $(
+                        #[cfg(all(any(target_arch = "x86",
+                                      target_arch = "x86_64"),
+                                  target_feature = $xf))]
+                        {
+                            // There is no truncating `pavg`: undo the
+                            // rounding when the sum is odd.
+                            let r = unsafe { ::vendor::$pavg(self, other) };
+                            r - ((self ^ other) & $ty::splat(1))
+                        }
+                    )*
+                    $(
+                        #[cfg(all(any(target_arch = "arm",
+                                      target_arch = "aarch64"),
+                                  target_feature = $af))]
+                        {
+                            unsafe { ::vendor::$hadd(self, other) }
+                        }
+                    )*
+                    #[cfg(not(any(
+                        $(all(any(target_arch = "x86",
+                                  target_arch = "x86_64"),
+                              target_feature = $xf),)*
+                        $(all(any(target_arch = "arm",
+                                  target_arch = "aarch64"),
+                              target_feature = $af),)*
+                    )))]
+                    {
+                        (self & other) + ((self ^ other) >> 1)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! define_mul_q15_round {
+    ($(($ty:ident $(, x86($xf:tt): $mulhrs:ident)*
+                  $(, arm($af:tt): $qrdmulh:ident)*)),+) => {
+        $(
+            impl $ty {
+                /// Lane-wise rounding multiplication of Q15 fixed-point
+                /// numbers: `(self * other + (1 << 14)) >> 15`.
+                ///
+                /// The only product that does not fit, `-1.0 * -1.0`,
+                /// saturates to `i16::MAX`.
+                #[inline(always)]
+                pub fn mul_q15_round(self, other: $ty) -> $ty {
+                    $(
+                        #[cfg(all(any(target_arch = "x86",
+                                      target_arch = "x86_64"),
+                                  target_feature = $xf))]
+                        {
+                            // `pmulhrsw` wraps `-1.0 * -1.0` around to
+                            // `i16::MIN`, which no other product produces:
+                            // flip it to `i16::MAX`.
+                            let r = unsafe { ::vendor::$mulhrs(self, other) };
+                            r ^ r.eq($ty::splat(::core::i16::MIN))
+                        }
+                    )*
+                    $(
+                        #[cfg(all(any(target_arch = "arm",
+                                      target_arch = "aarch64"),
+                                  target_feature = $af))]
+                        {
+                            unsafe { ::vendor::$qrdmulh(self, other) }
+                        }
+                    )*
+                    #[cfg(not(any(
+                        $(all(any(target_arch = "x86",
+                                  target_arch = "x86_64"),
+                              target_feature = $xf),)*
+                        $(all(any(target_arch = "arm",
+                                  target_arch = "aarch64"),
+                              target_feature = $af),)*
+                    )))]
+                    {
+                        let mut r = self;
+                        for i in 0..$ty::len() as u32 {
+                            let p = i32::from(self.extract(i))
+                                * i32::from(other.extract(i));
+                            let p = (p + (1 << 14)) >> 15;
+                            let p = if p > i32::from(::core::i16::MAX) {
+                                ::core::i16::MAX
+                            } else {
+                                p as i16
+                            };
+                            r = r.replace(i, p);
+                        }
+                        r
+                    }
+                }
+            }
+        )+
+    }
+}
+
+/// The `simd_shuffleN` indices selecting lanes `$lanes + $offset` of the
+/// concatenation of the two operands.
+macro_rules! shuffle_idx {
+    ([$($i:tt),+] + $offset:expr) => {
+        [$($i + $offset),+]
+    }
+}
+
+/// Rotates the lanes of `$v` `$n` positions towards the first lane, with
+/// one constant shuffle per rotation amount.
+macro_rules! rotate_lanes {
+    ($shuffle:ident, $len:tt, $v:expr, $n:expr, [$($k:tt),+]) => {
+        rotate_lanes!(@arms $shuffle, $len, $v, $n, [$($k),+], [$($k),+])
+    };
+    (@arms $shuffle:ident, $len:tt, $v:expr, $n:expr, [$($k:tt),+],
+     $lanes:tt) => {
+        {
+            let v = $v;
+            match $n {
+                $(
+                    $k => {
+                        const IDX: [u32; $len] = shuffle_idx!($lanes + $k);
+                        unsafe { $shuffle(v, v, IDX) }
+                    }
+                )+
+                _ => unreachable!(),
+            }
+        }
+    };
+}
+
+macro_rules! define_scan {
+    ($shuffle:ident, $len:tt, $lanes:tt, [$($step:tt),+]:
+     $(($ty:ident, $elem:ident)),+) => {
+        $(
+            impl $ty {
+                /// Inclusive prefix sum: lane `i` of the result is the sum of
+                /// lanes `0..=i`.
+                ///
+                /// The sum is computed in `log2(len)` steps, each adding a
+                /// copy of the lanes shifted up by a constant shuffle.
+                #[inline(always)]
+                pub fn prefix_sum_inclusive(self) -> $ty {
+                    let zero = $ty::splat(0 as $elem);
+                    let mut x = self;
+                    $(
+                        let up: $ty = {
+                            const IDX: [u32; $len] =
+                                shuffle_idx!($lanes + ($len - $step));
+                            unsafe { $shuffle(zero, x, IDX) }
+                        };
+                        x = x + up;
+                    )+
+                    x
+                }
+
+                /// Exclusive prefix sum: lane `i` of the result is the sum of
+                /// lanes `0..i`, so the first lane is zero.
+                #[inline(always)]
+                pub fn prefix_sum_exclusive(self) -> $ty {
+                    let zero = $ty::splat(0 as $elem);
+                    const IDX: [u32; $len] = shuffle_idx!($lanes + ($len - 1));
+                    let up: $ty = unsafe { $shuffle(zero, self, IDX) };
+                    up.prefix_sum_inclusive()
+                }
+
+                /// Rotates the lanes `n` positions towards the first lane:
+                /// lane `i` of the result is lane `(i + n) % len` of `self`.
+                #[inline(always)]
+                pub fn rotate_lanes_left(self, n: u32) -> $ty {
+                    rotate_lanes!($shuffle, $len, self, n % $len, $lanes)
+                }
+
+                /// Rotates the lanes `n` positions towards the last lane:
+                /// lane `(i + n) % len` of the result is lane `i` of `self`.
+                #[inline(always)]
+                pub fn rotate_lanes_right(self, n: u32) -> $ty {
+                    let n = $len - n % $len;
+                    rotate_lanes!($shuffle, $len, self, n % $len, $lanes)
+                }
+            }
+        )+
+    }
+}
+
+/// Lanes of `$v` permuted as described by the nibbles of `$idx`; see the
+/// `compress` module.
+macro_rules! permute_portable {
+    ($v:expr, $idx:expr, $ty:ident, $elem:ident) => {
+        {
+            let (v, idx) = ($v, $idx);
+            let mut r = $ty::splat(0 as $elem);
+            for i in 0..$ty::len() as u32 {
+                let n = (idx >> (4 * i)) & 0xf;
+                if n < 8 {
+                    r = r.replace(i, v.extract(n));
+                }
+            }
+            r
+        }
+    }
+}
+
+/// Table-driven permutation of vectors with four 32-bit lanes.
+macro_rules! permute_4x32 {
+    ($v:expr, $idx:expr, $ty:ident, $elem:ident) => {
+        {
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                      target_feature = "ssse3"))]
+            {
+                use core::mem::transmute;
+                let b = ::compress::shuffle_bytes_4x32($idx);
+                unsafe {
+                    let r = ::vendor::_mm_shuffle_epi8(transmute($v), b);
+                    transmute::<_, $ty>(r)
+                }
+            }
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            {
+                use core::mem::transmute;
+                let b = ::compress::shuffle_bytes_4x32($idx);
+                unsafe {
+                    let r = ::vendor::vqtbl1q_u8(transmute($v), b);
+                    transmute::<_, $ty>(r)
+                }
+            }
+            #[cfg(not(any(
+                all(any(target_arch = "x86", target_arch = "x86_64"),
+                    target_feature = "ssse3"),
+                all(target_arch = "aarch64", target_feature = "neon"))))]
+            {
+                permute_portable!($v, $idx, $ty, $elem)
+            }
+        }
+    }
+}
+
+/// Table-driven permutation of vectors with eight 16-bit lanes.
+macro_rules! permute_8x16 {
+    ($v:expr, $idx:expr, $ty:ident, $elem:ident) => {
+        {
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                      target_feature = "ssse3"))]
+            {
+                use core::mem::transmute;
+                let b = ::compress::shuffle_bytes_8x16($idx);
+                unsafe {
+                    let r = ::vendor::_mm_shuffle_epi8(transmute($v), b);
+                    transmute::<_, $ty>(r)
+                }
+            }
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            {
+                use core::mem::transmute;
+                let b = ::compress::shuffle_bytes_8x16($idx);
+                unsafe {
+                    let r = ::vendor::vqtbl1q_u8(transmute($v), b);
+                    transmute::<_, $ty>(r)
+                }
+            }
+            #[cfg(not(any(
+                all(any(target_arch = "x86", target_arch = "x86_64"),
+                    target_feature = "ssse3"),
+                all(target_arch = "aarch64", target_feature = "neon"))))]
+            {
+                permute_portable!($v, $idx, $ty, $elem)
+            }
+        }
+    }
+}
+
+/// Table-driven permutation of vectors with eight 32-bit lanes.
+macro_rules! permute_8x32 {
+    ($v:expr, $idx:expr, $ty:ident, $elem:ident) => {
+        {
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                      target_feature = "avx2"))]
+            {
+                use core::mem::transmute;
+                let (p, keep) = ::compress::permute_8x32($idx);
+                unsafe {
+                    let r = ::vendor::_mm256_permutevar8x32_epi32(
+                        transmute($v), p);
+                    transmute::<_, $ty>(r & keep)
+                }
+            }
+            #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"),
+                          target_feature = "avx2")))]
+            {
+                permute_portable!($v, $idx, $ty, $elem)
+            }
+        }
+    }
+}
+
+macro_rules! define_compress_common {
+    ($ty:ident, $elem:ident, $boolname:ident) => {
+        impl $ty {
+            /// Bitmask of the lanes of `mask` that are not zero.
+            #[inline(always)]
+            fn mask_bits(mask: ::simd::$boolname) -> u64 {
+                let mut bits = 0;
+                for i in 0..$ty::len() as u32 {
+                    if mask.extract(i) != 0 {
+                        bits |= 1 << i;
+                    }
+                }
+                bits
+            }
+
+            /// Lanes of `a` where `mask` is set and of `b` elsewhere.
+            #[inline(always)]
+            fn select_lanes(
+                mask: ::simd::$boolname, a: $ty, b: $ty
+            ) -> $ty {
+                use core::mem::transmute;
+                use simd::$boolname;
+                let m = mask.ne($boolname::splat(0));
+                unsafe {
+                    let a: $boolname = transmute(a);
+                    let b: $boolname = transmute(b);
+                    transmute((a & m) | (b & !m))
+                }
+            }
+
+            /// Writes the lanes of `self` selected by `mask` to the front of
+            /// `slice`, and returns how many were written.
+            ///
+            /// # Panics
+            ///
+            /// If `slice` is too short to hold the selected lanes.
+            #[inline(always)]
+            pub fn compress_store(
+                self, slice: &mut [$elem], mask: ::simd::$boolname
+            ) -> usize {
+                let n = $ty::mask_bits(mask).count_ones() as usize;
+                assert!(slice.len() >= n);
+                let r = self.compress(mask);
+                for (i, x) in slice[..n].iter_mut().enumerate() {
+                    *x = r.extract(i as u32);
+                }
+                n
+            }
+        }
+    }
+}
+
+macro_rules! define_compress {
+    (portable: $(($ty:ident, $elem:ident, $boolname:ident)),+) => {
+        $(
+            define_compress_common!($ty, $elem, $boolname);
+
+            impl $ty {
+                /// Packs the lanes of `self` where `mask` is set (not zero)
+                /// into the first lanes of the result, preserving their
+                /// order. The remaining lanes are zero.
+                #[inline(always)]
+                pub fn compress(self, mask: ::simd::$boolname) -> $ty {
+                    let mut r = $ty::splat(0 as $elem);
+                    let mut j = 0;
+                    for i in 0..$ty::len() as u32 {
+                        if mask.extract(i) != 0 {
+                            r = r.replace(j, self.extract(i));
+                            j += 1;
+                        }
+                    }
+                    r
+                }
+
+                /// The inverse of `compress`: the first lanes of `self` are
+                /// moved, in order, to the lanes where `mask` is set. The
+                /// other lanes are taken from `src`.
+                #[inline(always)]
+                pub fn expand(self, mask: ::simd::$boolname, src: $ty) -> $ty {
+                    let mut r = src;
+                    let mut j = 0;
+                    for i in 0..$ty::len() as u32 {
+                        if mask.extract(i) != 0 {
+                            r = r.replace(i, self.extract(j));
+                            j += 1;
+                        }
+                    }
+                    r
+                }
+            }
+        )+
+    };
+    ($permute:ident, $compress:ident, $expand:ident:
+     $(($ty:ident, $elem:ident, $boolname:ident)),+) => {
+        $(
+            define_compress_common!($ty, $elem, $boolname);
+
+            impl $ty {
+                /// Packs the lanes of `self` where `mask` is set (not zero)
+                /// into the first lanes of the result, preserving their
+                /// order. The remaining lanes are zero.
+                #[inline(always)]
+                pub fn compress(self, mask: ::simd::$boolname) -> $ty {
+                    let bits = $ty::mask_bits(mask) as usize;
+                    let idx = u32::from(::compress::$compress[bits]);
+                    $permute!(self, idx, $ty, $elem)
+                }
+
+                /// The inverse of `compress`: the first lanes of `self` are
+                /// moved, in order, to the lanes where `mask` is set. The
+                /// other lanes are taken from `src`.
+                #[inline(always)]
+                pub fn expand(self, mask: ::simd::$boolname, src: $ty) -> $ty {
+                    let bits = $ty::mask_bits(mask) as usize;
+                    let idx = u32::from(::compress::$expand[bits]);
+                    let r = $permute!(self, idx, $ty, $elem);
+                    $ty::select_lanes(mask, r, src)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! define_min_max {
+    ($($ty:ident),+) => {
+        $(
+            impl $ty {
+                /// Lane-wise minimum.
+                ///
+                /// If the lanes are unordered (one of them is NaN), the lane
+                /// of `other` is returned.
+                #[inline(always)]
+                pub fn min(self, other: $ty) -> $ty {
+                    $ty::select_lanes(self.lt(other), self, other)
+                }
+
+                /// Lane-wise maximum.
+                ///
+                /// The exact complement of `min`: if the lanes are unordered
+                /// (one of them is NaN), the lane of `self` is returned, so
+                /// that `a.min(b)` and `a.max(b)` always hold both lanes.
+                #[inline(always)]
+                pub fn max(self, other: $ty) -> $ty {
+                    $ty::select_lanes(self.lt(other), other, self)
+                }
+            }
+        )+
+    }
+}
+
+/// The `simd_shuffleN` indices of `$lanes ^ $j`.
+macro_rules! xor_idx {
+    ([$($i:tt),+] ^ $j:expr) => {
+        [$($i ^ $j),+]
+    }
+}
+
+/// The `simd_shuffleN` indices blending the `lo` and `hi` vectors of a
+/// compare-exchange step of a bitonic network over `$n` lanes: lane `i`
+/// takes `hi` iff bits `$k` and `$j` of `i` differ, flipped by `$desc`.
+macro_rules! exchange_idx {
+    ([$($i:tt),+], $n:tt, $k:expr, $j:expr, $desc:expr) => {
+        [$($i + $n * ((($i / $k) ^ ($i / $j) ^ $desc) & 1)),+]
+    }
+}
+
+/// One compare-exchange step of a bitonic network: lane `i` of `$x` is
+/// paired with lane `i ^ $j`, see `exchange_idx`.
+macro_rules! compare_exchange {
+    ($shuffle:ident, $ty:ident, $n:tt, $lanes:tt, $x:expr,
+     $k:expr, $j:expr, $desc:expr) => {
+        {
+            const PAIR: [u32; $n] = xor_idx!($lanes ^ $j);
+            const BLEND: [u32; $n] =
+                exchange_idx!($lanes, $n, $k, $j, $desc);
+            let x = $x;
+            let p: $ty = unsafe { $shuffle(x, x, PAIR) };
+            // In each pair, the lane of `hi` is the complement of the lane
+            // of `lo`, even if they are unordered: the pair is either
+            // swapped or kept.
+            let (lo, hi) = (x.min(p), p.max(x));
+            let r: $ty = unsafe { $shuffle(lo, hi, BLEND) };
+            r
+        }
+    }
+}
+
+/// Bitonic sorting network over the lanes of `$x`, one compare-exchange
+/// step per `($k, $j)` stage.
+macro_rules! sort_network {
+    ($shuffle:ident, $ty:ident, $n:tt, $lanes:tt, $x:expr,
+     [$(($k:tt, $j:tt)),+], $desc:expr) => {
+        {
+            let mut x = $x;
+            $(
+                x = compare_exchange!(
+                    $shuffle, $ty, $n, $lanes, x, $k, $j, $desc
+                );
+            )+
+            x
+        }
+    }
+}
+
+macro_rules! define_sort {
+    ($shuffle:ident, $nelems:tt, $lanes:tt, $stages:tt, $merge:tt:
+     $(($ty:ident, $elem:ident, $pad:expr)),+) => {
+        $(
+            impl $ty {
+                /// Lane `i` of the result is lane `len - 1 - i` of `self`.
+                #[inline(always)]
+                fn reverse_lanes(self) -> $ty {
+                    const IDX: [u32; $nelems] =
+                        xor_idx!($lanes ^ ($nelems - 1));
+                    unsafe { $shuffle(self, self, IDX) }
+                }
+
+                /// Sorts a bitonic sequence of lanes in ascending order.
+                #[inline(always)]
+                fn bitonic_merge(self) -> $ty {
+                    sort_network!(
+                        $shuffle, $ty, $nelems, $lanes, self, $merge, 0
+                    )
+                }
+
+                /// Sorts the lanes in ascending order.
+                ///
+                /// NaN lanes end up in unspecified positions, but the
+                /// result is always a permutation of the lanes.
+                #[inline(always)]
+                pub fn sort_lanes(self) -> $ty {
+                    sort_network!(
+                        $shuffle, $ty, $nelems, $lanes, self, $stages, 0
+                    )
+                }
+
+                /// Sorts the lanes in descending order.
+                ///
+                /// NaN lanes end up in unspecified positions, but the
+                /// result is always a permutation of the lanes.
+                #[inline(always)]
+                pub fn sort_lanes_desc(self) -> $ty {
+                    sort_network!(
+                        $shuffle, $ty, $nelems, $lanes, self, $stages, 1
+                    )
+                }
+
+                /// Merges two vectors whose lanes are sorted in ascending
+                /// order. The first vector of the result holds the smallest
+                /// half of the lanes and the second the largest half, both
+                /// sorted.
+                #[inline(always)]
+                pub fn merge_sorted(a: $ty, b: $ty) -> ($ty, $ty) {
+                    let b = b.reverse_lanes();
+                    let (lo, hi) = (a.min(b), a.max(b));
+                    (lo.bitonic_merge(), hi.bitonic_merge())
+                }
+
+                /// Sorts a slice of at most 64 elements in ascending order
+                /// using the in-register sorting networks.
+                ///
+                /// NaN elements end up in unspecified positions, but no
+                /// element is lost or duplicated.
+                ///
+                /// # Panics
+                ///
+                /// If `slice` has more than 64 elements.
+                pub fn sort_slice(slice: &mut [$elem]) {
+                    const N: usize = $nelems;
+                    assert!(slice.len() <= 64);
+
+                    // Round the number of vectors up to a power of two,
+                    // padding with elements that sort last:
+                    let mut m = 1;
+                    while m * N < slice.len() {
+                        m *= 2;
+                    }
+                    let mut v = [$ty::splat($pad); 64 / N];
+                    for (i, &x) in slice.iter().enumerate() {
+                        v[i / N] = v[i / N].replace((i % N) as u32, x);
+                    }
+
+                    for x in &mut v[..m] {
+                        *x = x.sort_lanes();
+                    }
+
+                    // Bitonic merges of runs of `run` sorted vectors:
+                    let mut run = 1;
+                    while run < m {
+                        let mut base = 0;
+                        while base < m {
+                            let v = &mut v[base..base + 2 * run];
+                            {
+                                let hi = &mut v[run..];
+                                hi.reverse();
+                                for x in hi.iter_mut() {
+                                    *x = x.reverse_lanes();
+                                }
+                            }
+                            let mut s = run;
+                            while s > 0 {
+                                for i in 0..2 * run {
+                                    if i & s == 0 {
+                                        let (a, b) = (v[i], v[i + s]);
+                                        v[i] = a.min(b);
+                                        v[i + s] = a.max(b);
+                                    }
+                                }
+                                s /= 2;
+                            }
+                            for x in v.iter_mut() {
+                                *x = x.bitonic_merge();
+                            }
+                            base += 2 * run;
+                        }
+                        run *= 2;
+                    }
+
+                    // With NaNs the padding is not necessarily sorted last:
+                    // copy back everything but `m * N - len` padding
+                    // elements, wherever they are.
+                    let mut pad = m * N - slice.len();
+                    let mut out = slice.iter_mut().rev();
+                    for i in (0..m * N).rev() {
+                        let x = v[i / N].extract((i % N) as u32);
+                        if pad > 0 && x == $pad {
+                            pad -= 1;
+                        } else if let Some(o) = out.next() {
+                            *o = x;
+                        }
+                    }
+                }
+            }
+        )+
+    }
+}
+
+/// `transpose4x4` for vectors with 4 lanes.
+///
+/// On ARM the rows are interleaved with `vtrn` and the 64-bit halves are
+/// then recombined. Elsewhere the `unpcklo`/`unpckhi` + `movlh`/`movhl`
+/// sequence of `_MM_TRANSPOSE4_PS` is used; with AVX it lowers to
+/// `vunpck` + `vperm2f128` for 64-bit lanes.
+macro_rules! define_transpose4x4 {
+    ($($ty:ident),+) => {
+        $(
+            impl $ty {
+                /// Transposes the 4x4 matrix whose rows are `rows`.
+                #[inline(always)]
+                pub fn transpose4x4(rows: [$ty; 4]) -> [$ty; 4] {
+                    let (r0, r1) = (rows[0], rows[1]);
+                    let (r2, r3) = (rows[2], rows[3]);
+                    #[cfg(any(target_arch = "arm",
+                              target_arch = "aarch64"))]
+                    unsafe {
+                        // vtrn
+                        let t0: $ty = simd_shuffle4(r0, r1, [0, 4, 2, 6]);
+                        let t1: $ty = simd_shuffle4(r0, r1, [1, 5, 3, 7]);
+                        let t2: $ty = simd_shuffle4(r2, r3, [0, 4, 2, 6]);
+                        let t3: $ty = simd_shuffle4(r2, r3, [1, 5, 3, 7]);
+                        // vcombine of the low / high halves
+                        [
+                            simd_shuffle4(t0, t2, [0, 1, 4, 5]),
+                            simd_shuffle4(t1, t3, [0, 1, 4, 5]),
+                            simd_shuffle4(t0, t2, [2, 3, 6, 7]),
+                            simd_shuffle4(t1, t3, [2, 3, 6, 7]),
+                        ]
+                    }
+                    #[cfg(not(any(target_arch = "arm",
+                                  target_arch = "aarch64")))]
+                    unsafe {
+                        // unpcklo / unpckhi
+                        let t0: $ty = simd_shuffle4(r0, r1, [0, 4, 1, 5]);
+                        let t1: $ty = simd_shuffle4(r0, r1, [2, 6, 3, 7]);
+                        let t2: $ty = simd_shuffle4(r2, r3, [0, 4, 1, 5]);
+                        let t3: $ty = simd_shuffle4(r2, r3, [2, 6, 3, 7]);
+                        // movlh / movhl
+                        [
+                            simd_shuffle4(t0, t2, [0, 1, 4, 5]),
+                            simd_shuffle4(t0, t2, [2, 3, 6, 7]),
+                            simd_shuffle4(t1, t3, [0, 1, 4, 5]),
+                            simd_shuffle4(t1, t3, [2, 3, 6, 7]),
+                        ]
+                    }
+                }
+            }
+        )+
+    }
+}
+
+/// `transpose8x8` for vectors with 8 16-bit lanes.
+///
+/// On ARM this is the `vtrn.16`, `vtrn.32`, `vcombine` sequence, elsewhere
+/// the `punpck{l,h}wd`, `punpck{l,h}dq`, `punpck{l,h}qdq` one.
+macro_rules! define_transpose8x8 {
+    ($($ty:ident),+) => {
+        $(
+            impl $ty {
+                /// Transposes the 8x8 matrix whose rows are `rows`.
+                #[inline(always)]
+                pub fn transpose8x8(rows: [$ty; 8]) -> [$ty; 8] {
+                    let (r0, r1) = (rows[0], rows[1]);
+                    let (r2, r3) = (rows[2], rows[3]);
+                    let (r4, r5) = (rows[4], rows[5]);
+                    let (r6, r7) = (rows[6], rows[7]);
+                    #[cfg(any(target_arch = "arm",
+                              target_arch = "aarch64"))]
+                    unsafe {
+                        // vtrn.16
+                        const TRN16_LO: [u32; 8] = [0, 8, 2, 10, 4, 12, 6, 14];
+                        const TRN16_HI: [u32; 8] = [1, 9, 3, 11, 5, 13, 7, 15];
+                        let t0: $ty = simd_shuffle8(r0, r1, TRN16_LO);
+                        let t1: $ty = simd_shuffle8(r0, r1, TRN16_HI);
+                        let t2: $ty = simd_shuffle8(r2, r3, TRN16_LO);
+                        let t3: $ty = simd_shuffle8(r2, r3, TRN16_HI);
+                        let t4: $ty = simd_shuffle8(r4, r5, TRN16_LO);
+                        let t5: $ty = simd_shuffle8(r4, r5, TRN16_HI);
+                        let t6: $ty = simd_shuffle8(r6, r7, TRN16_LO);
+                        let t7: $ty = simd_shuffle8(r6, r7, TRN16_HI);
+                        // vtrn.32
+                        const TRN32_LO: [u32; 8] = [0, 1, 8, 9, 4, 5, 12, 13];
+                        const TRN32_HI: [u32; 8] =
+                            [2, 3, 10, 11, 6, 7, 14, 15];
+                        let v0: $ty = simd_shuffle8(t0, t2, TRN32_LO);
+                        let v1: $ty = simd_shuffle8(t1, t3, TRN32_LO);
+                        let v2: $ty = simd_shuffle8(t0, t2, TRN32_HI);
+                        let v3: $ty = simd_shuffle8(t1, t3, TRN32_HI);
+                        let v4: $ty = simd_shuffle8(t4, t6, TRN32_LO);
+                        let v5: $ty = simd_shuffle8(t5, t7, TRN32_LO);
+                        let v6: $ty = simd_shuffle8(t4, t6, TRN32_HI);
+                        let v7: $ty = simd_shuffle8(t5, t7, TRN32_HI);
+                        // vcombine of the low / high halves
+                        const COMBINE_LO: [u32; 8] =
+                            [0, 1, 2, 3, 8, 9, 10, 11];
+                        const COMBINE_HI: [u32; 8] =
+                            [4, 5, 6, 7, 12, 13, 14, 15];
+                        [
+                            simd_shuffle8(v0, v4, COMBINE_LO),
+                            simd_shuffle8(v1, v5, COMBINE_LO),
+                            simd_shuffle8(v2, v6, COMBINE_LO),
+                            simd_shuffle8(v3, v7, COMBINE_LO),
+                            simd_shuffle8(v0, v4, COMBINE_HI),
+                            simd_shuffle8(v1, v5, COMBINE_HI),
+                            simd_shuffle8(v2, v6, COMBINE_HI),
+                            simd_shuffle8(v3, v7, COMBINE_HI),
+                        ]
+                    }
+                    #[cfg(not(any(target_arch = "arm",
+                                  target_arch = "aarch64")))]
+                    unsafe {
+                        // punpck{l,h}wd
+                        const UNPCK16_LO: [u32; 8] =
+                            [0, 8, 1, 9, 2, 10, 3, 11];
+                        const UNPCK16_HI: [u32; 8] =
+                            [4, 12, 5, 13, 6, 14, 7, 15];
+                        let s0: $ty = simd_shuffle8(r0, r1, UNPCK16_LO);
+                        let s1: $ty = simd_shuffle8(r0, r1, UNPCK16_HI);
+                        let s2: $ty = simd_shuffle8(r2, r3, UNPCK16_LO);
+                        let s3: $ty = simd_shuffle8(r2, r3, UNPCK16_HI);
+                        let s4: $ty = simd_shuffle8(r4, r5, UNPCK16_LO);
+                        let s5: $ty = simd_shuffle8(r4, r5, UNPCK16_HI);
+                        let s6: $ty = simd_shuffle8(r6, r7, UNPCK16_LO);
+                        let s7: $ty = simd_shuffle8(r6, r7, UNPCK16_HI);
+                        // punpck{l,h}dq
+                        const UNPCK32_LO: [u32; 8] =
+                            [0, 1, 8, 9, 2, 3, 10, 11];
+                        const UNPCK32_HI: [u32; 8] =
+                            [4, 5, 12, 13, 6, 7, 14, 15];
+                        let u0: $ty = simd_shuffle8(s0, s2, UNPCK32_LO);
+                        let u1: $ty = simd_shuffle8(s0, s2, UNPCK32_HI);
+                        let u2: $ty = simd_shuffle8(s1, s3, UNPCK32_LO);
+                        let u3: $ty = simd_shuffle8(s1, s3, UNPCK32_HI);
+                        let u4: $ty = simd_shuffle8(s4, s6, UNPCK32_LO);
+                        let u5: $ty = simd_shuffle8(s4, s6, UNPCK32_HI);
+                        let u6: $ty = simd_shuffle8(s5, s7, UNPCK32_LO);
+                        let u7: $ty = simd_shuffle8(s5, s7, UNPCK32_HI);
+                        // punpck{l,h}qdq
+                        const UNPCK64_LO: [u32; 8] =
+                            [0, 1, 2, 3, 8, 9, 10, 11];
+                        const UNPCK64_HI: [u32; 8] =
+                            [4, 5, 6, 7, 12, 13, 14, 15];
+                        [
+                            simd_shuffle8(u0, u4, UNPCK64_LO),
+                            simd_shuffle8(u0, u4, UNPCK64_HI),
+                            simd_shuffle8(u1, u5, UNPCK64_LO),
+                            simd_shuffle8(u1, u5, UNPCK64_HI),
+                            simd_shuffle8(u2, u6, UNPCK64_LO),
+                            simd_shuffle8(u2, u6, UNPCK64_HI),
+                            simd_shuffle8(u3, u7, UNPCK64_LO),
+                            simd_shuffle8(u3, u7, UNPCK64_HI),
+                        ]
+                    }
+                }
+            }
+        )+
+    }
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_arithmetic_ {
+        ($tn:ident, $zero:expr, $one:expr, $two:expr, $four:expr) => {
+            {
+                 let z = $tn::splat($zero);
+                 let o = $tn::splat($one);
+                 let t = $tn::splat($two);
+                 let f = $tn::splat($four);
+
+                 // add
+                 assert_eq!(z + z, z);
+                 assert_eq!(o + z, o);
+                 assert_eq!(t + z, t);
+                 assert_eq!(t + t, f);
+                 // sub
+                 assert_eq!(z - z, z);
+                 assert_eq!(o - z, o);
+                 assert_eq!(t - z, t);
+                 assert_eq!(f - t, t);
+                 assert_eq!(f - o - o, t);
+                 // mul
+                 assert_eq!(z * z, z);
+                 assert_eq!(z * o, z);
+                 assert_eq!(z * t, z);
+                 assert_eq!(o * t, t);
+                 assert_eq!(t * t, f);
+                 // div
+                 assert_eq!(z / o, z);
+                 assert_eq!(t / o, t);
+                 assert_eq!(f / o, f);
+                 assert_eq!(t / t, o);
+                 assert_eq!(f / t, t);
+                 // rem
+                 assert_eq!(o % o, z);
+                 assert_eq!(f % t, z);
+
+                {
+                    let mut v = z;
+                    assert_eq!(v, z);
+                    v += o;  // add_assign
+                    assert_eq!(v, o);
+                    v -= o; // sub_assign
+                    assert_eq!(v, z);
+                    v = t;
+                    v *= o; // mul_assign
+                    assert_eq!(v, t);
+                    v *= t;
+                    assert_eq!(v, f);
+                    v /= o; // div_assign
+                    assert_eq!(v, f);
+                    v /= t;
+                    assert_eq!(v, t);
+                    v %= t; // rem_assign
+                    assert_eq!(v, z);
+                }
+            }
+        };
+    }
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_neg_ {
+        ($tn:ident, $zero:expr, $one:expr, $two:expr, $four:expr) => {
+            {
+                let z = $tn::splat($zero);
+                let o = $tn::splat($one);
+                let t = $tn::splat($two);
+                let f = $tn::splat($four);
+
+                let nz = $tn::splat(-$zero);
+                let no = $tn::splat(-$one);
+                let nt = $tn::splat(-$two);
+                let nf = $tn::splat(-$four);
+
+                assert_eq!(-z, nz);
+                assert_eq!(-o, no);
+                assert_eq!(-t, nt);
+                assert_eq!(-f, nf);
+            }
+        };
+    }
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_bit_arithmetic_ {
+    ($tn:ident) => {
+        {
+            let z = $tn::splat(0);
+            let o = $tn::splat(1);
+            let t = $tn::splat(2);
+            let f = $tn::splat(4);
+            let m = $tn::splat(!z.extract(0));
+
+            // shr
+            assert_eq!(o >> 1, z);
+            assert_eq!(t >> 1, o);
+            assert_eq!(f >> 1, t);
+            // shl
+            assert_eq!(o << 1, t);
+            assert_eq!(o << 2, f);
+            assert_eq!(t << 1, f);
+            // bitand
+            assert_eq!(o & o, o);
+            assert_eq!(t & t, t);
+            assert_eq!(t & o, z);
+            // bitor
+            assert_eq!(o | o, o);
+            assert_eq!(t | t, t);
             assert_eq!(z | o, o);
             // bitxor
             assert_eq!(o ^ o, z);
@@ -510,65 +2195,868 @@ macro_rules! test_bit_arithmetic_ {
             assert_eq!(!z, m);
             assert_eq!(!m, z);
 
-            {  // shr_assign
-                let mut v = o;
-                v >>= 1;
-                assert_eq!(v, z);
+            {  // shr_assign
+                let mut v = o;
+                v >>= 1;
+                assert_eq!(v, z);
+            }
+            {  // shl_assign
+                let mut v = o;
+                v <<= 1;
+                assert_eq!(v, t);
+            }
+            {  // and_assign
+                let mut v = o;
+                v &= t;
+                assert_eq!(v, z);
+            }
+            {  // or_assign
+                let mut v = z;
+                v |= o;
+                assert_eq!(v, o);
+            }
+            {  // xor_assign
+                let mut v = z;
+                v ^= o;
+                assert_eq!(v, o);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_ops_si {
+        ($($tn:ident),+) => {
+            $(
+                test_arithmetic_!($tn, 0, 1, 2, 4);
+                test_neg_!($tn, 0, 1, 2, 4);
+                test_bit_arithmetic_!($tn);
+            )+
+        };
+    }
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_ops_ui {
+        ($($tn:ident),+) => {
+            $(
+                test_arithmetic_!($tn, 0, 1, 2, 4);
+                test_bit_arithmetic_!($tn);
+            )+
+        };
+    }
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_ops_f {
+        ($($tn:ident),+)  => {
+            $(
+                test_arithmetic_!($tn, 0., 1., 2., 4.);
+                test_neg_!($tn, 0., 1., 2., 4.);
+            )+
+        };
+    }
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_abs_diff {
+    ($(($tn:ident, $elem:ident, $uelem:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as u32;
+                let mut a = $tn::splat(0);
+                let mut b = $tn::splat(0);
+                for i in 0..n {
+                    let x = i.wrapping_mul(37).wrapping_add(5);
+                    let y = i.wrapping_mul(91).wrapping_add(200);
+                    a = a.replace(i, x as $elem);
+                    b = b.replace(i, y as $elem);
+                }
+                a = a.replace(0, ::core::$elem::MIN);
+                b = b.replace(0, ::core::$elem::MAX);
+                let r = a.abs_diff(b);
+                for i in 0..n {
+                    let (x, y) = (a.extract(i) as i128, b.extract(i) as i128);
+                    assert_eq!(r.extract(i), (x - y).abs() as $uelem);
+                    assert_eq!(b.abs_diff(a).extract(i), r.extract(i));
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_sad_u8 {
+    ($(($tn:ident, $rn:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as u32;
+                let mut a = $tn::splat(0);
+                let mut b = $tn::splat(0);
+                for i in 0..n {
+                    a = a.replace(i, i.wrapping_mul(37) as u8);
+                    let y = i.wrapping_mul(113).wrapping_add(7);
+                    b = b.replace(i, y as u8);
+                }
+                let r: $rn = a.sad_u8(b);
+                for i in 0..$rn::len() as u32 {
+                    let mut e = 0_u64;
+                    for j in 8 * i..8 * i + 8 {
+                        let x = i64::from(a.extract(j));
+                        let y = i64::from(b.extract(j));
+                        e += (x - y).abs() as u64;
+                    }
+                    assert_eq!(r.extract(i), e);
+                }
+                assert_eq!(a.sad_u8(a), $rn::splat(0));
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_avg {
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as u32;
+                let mut a = $tn::splat(0);
+                let mut b = $tn::splat(0);
+                for i in 0..n {
+                    let x = i.wrapping_mul(37).wrapping_add(5);
+                    let y = i.wrapping_mul(91).wrapping_add(200);
+                    a = a.replace(i, x as $elem);
+                    b = b.replace(i, y as $elem);
+                }
+                a = a.replace(0, ::core::$elem::MAX);
+                b = b.replace(0, ::core::$elem::MAX);
+                a = a.replace(1, ::core::$elem::MAX);
+                b = b.replace(1, ::core::$elem::MAX - 1);
+                let round = a.avg_round(b);
+                let floor = a.avg_floor(b);
+                for i in 0..n {
+                    let (x, y) = (a.extract(i) as u128, b.extract(i) as u128);
+                    assert_eq!(round.extract(i), ((x + y + 1) >> 1) as $elem);
+                    assert_eq!(floor.extract(i), ((x + y) >> 1) as $elem);
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_mul_q15_round {
+    ($($tn:ident),+) => {
+        $(
+            {
+                let values = [
+                    ::core::i16::MIN, ::core::i16::MIN + 1, -16384, -3, -1,
+                    0, 1, 2, 3, 16384, 16385, ::core::i16::MAX - 1,
+                    ::core::i16::MAX,
+                ];
+                let n = $tn::len() as usize;
+                for (k, &x) in values.iter().enumerate() {
+                    let mut b = $tn::splat(0);
+                    for i in 0..n {
+                        let y = values[(k + i) % values.len()];
+                        b = b.replace(i as u32, y);
+                    }
+                    let r = $tn::splat(x).mul_q15_round(b);
+                    for i in 0..n {
+                        let y = i32::from(b.extract(i as u32));
+                        let e = (i32::from(x) * y + (1 << 14)) >> 15;
+                        let e = ::core::cmp::min(e, 32767) as i16;
+                        assert_eq!(r.extract(i as u32), e);
+                    }
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_scan {
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as u32;
+                let mut a = $tn::splat(0 as $elem);
+                for i in 0..n {
+                    a = a.replace(i, (i % 3) as $elem);
+                }
+                let inclusive = a.prefix_sum_inclusive();
+                let exclusive = a.prefix_sum_exclusive();
+                let mut sum = 0 as $elem;
+                for i in 0..n {
+                    assert_eq!(exclusive.extract(i), sum);
+                    sum += a.extract(i);
+                    assert_eq!(inclusive.extract(i), sum);
+                }
+
+                let mut b = $tn::splat(0 as $elem);
+                for i in 0..n {
+                    b = b.replace(i, i as $elem);
+                }
+                for k in 0..2 * n {
+                    let l = b.rotate_lanes_left(k);
+                    let r = b.rotate_lanes_right(k);
+                    for i in 0..n {
+                        assert_eq!(l.extract(i), b.extract((i + k) % n));
+                        assert_eq!(r.extract((i + k) % n), b.extract(i));
+                    }
+                    assert_eq!(l.rotate_lanes_right(k), b);
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_compress {
+    ($(($tn:ident, $elem:ident, $bn:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as u32;
+                let mut a = $tn::splat(0 as $elem);
+                let mut src = $tn::splat(0 as $elem);
+                for i in 0..n {
+                    a = a.replace(i, (i + 1) as $elem);
+                    src = src.replace(i, (i + 101) as $elem);
+                }
+                // All patterns for up to 8 lanes, a sample of the others:
+                let patterns: u64 = if n <= 8 { 1 << n } else { 256 };
+                for p in 0..patterns {
+                    let bits = if n <= 8 {
+                        p
+                    } else {
+                        p.wrapping_mul(0x9e37_79b9_7f4a_7c15)
+                    };
+                    let mut mask = $bn::splat(0);
+                    for i in 0..n {
+                        if bits & (1 << i) != 0 {
+                            mask = mask.replace(i, !0);
+                        }
+                    }
+
+                    let mut e_compress = [0 as $elem; 64];
+                    let mut e_expand = [0 as $elem; 64];
+                    let mut j = 0;
+                    for i in 0..n {
+                        e_expand[i as usize] = src.extract(i);
+                        if mask.extract(i) != 0 {
+                            e_compress[j as usize] = a.extract(i);
+                            e_expand[i as usize] = a.extract(j);
+                            j += 1;
+                        }
+                    }
+
+                    let c = a.compress(mask);
+                    let x = a.expand(mask, src);
+                    for i in 0..n {
+                        assert_eq!(c.extract(i), e_compress[i as usize]);
+                        assert_eq!(x.extract(i), e_expand[i as usize]);
+                    }
+
+                    let mut out = [0 as $elem; 64];
+                    let count = a.compress_store(&mut out[..j as usize], mask);
+                    assert_eq!(count, j as usize);
+                    assert_eq!(&out[..count], &e_compress[..count]);
+                }
             }
-            {  // shl_assign
-                let mut v = o;
-                v <<= 1;
-                assert_eq!(v, t);
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_sort {
+    (nan: $(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as usize;
+                let nan = 0. as $elem / 0.;
+                // `out` must hold the distinct values `0..out.len()`, but
+                // for `p` which is replaced by a NaN:
+                let check = |out: &[$elem], p: usize| {
+                    let mut seen = [false; 128];
+                    let mut nans = 0;
+                    for &x in out {
+                        if x.is_nan() {
+                            nans += 1;
+                        } else {
+                            let x = x as usize;
+                            assert!(x != p && !seen[x], "{:?}", out);
+                            seen[x] = true;
+                        }
+                    }
+                    assert_eq!(nans, 1, "{:?}", out);
+                };
+                let mut out = [0 as $elem; 128];
+
+                for p in 0..n {
+                    let mut a = $tn::splat(0 as $elem);
+                    for i in 0..n {
+                        let x = if i == p { nan } else { i as $elem };
+                        a = a.replace(((i * 5) % n) as u32, x);
+                    }
+                    for (i, x) in out[..n].iter_mut().enumerate() {
+                        *x = a.sort_lanes().extract(i as u32);
+                    }
+                    check(&out[..n], p);
+                    for (i, x) in out[..n].iter_mut().enumerate() {
+                        *x = a.sort_lanes_desc().extract(i as u32);
+                    }
+                    check(&out[..n], p);
+
+                    // merge_sorted:
+                    let mut a = $tn::splat(0 as $elem);
+                    let mut b = $tn::splat(0 as $elem);
+                    for i in 0..n {
+                        a = a.replace(i as u32, (2 * i) as $elem);
+                        b = b.replace(i as u32, (2 * i + 1) as $elem);
+                    }
+                    let a = a.replace(p as u32, nan);
+                    let (lo, hi) = $tn::merge_sorted(a, b);
+                    for i in 0..n {
+                        out[i] = lo.extract(i as u32);
+                        out[n + i] = hi.extract(i as u32);
+                    }
+                    check(&out[..2 * n], 2 * p);
+                }
+
+                // sort_slice:
+                for len in 1..65 {
+                    for p in 0..len {
+                        for (i, x) in out[..len].iter_mut().enumerate() {
+                            *x = if i == p { nan } else { i as $elem };
+                        }
+                        $tn::sort_slice(&mut out[..len]);
+                        check(&out[..len], p);
+                    }
+                }
             }
-            {  // and_assign
-                let mut v = o;
-                v &= t;
-                assert_eq!(v, z);
+        )+
+    };
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let n = $tn::len() as usize;
+                let check = |perm: &[usize]| {
+                    let mut a = $tn::splat(0 as $elem);
+                    let mut d = $tn::splat(0 as $elem);
+                    for i in 0..n {
+                        a = a.replace(i as u32, perm[i] as $elem);
+                        d = d.replace(i as u32, (perm[i] / 2) as $elem);
+                    }
+                    let (asc, desc) = (a.sort_lanes(), a.sort_lanes_desc());
+                    let dasc = d.sort_lanes();
+                    for i in 0..n {
+                        assert_eq!(asc.extract(i as u32), i as $elem);
+                        let e = (n - 1 - i) as $elem;
+                        assert_eq!(desc.extract(i as u32), e);
+                        assert_eq!(dasc.extract(i as u32), (i / 2) as $elem);
+                    }
+                };
+
+                let mut perm = [0_usize; 64];
+                for i in 0..n {
+                    perm[i] = i;
+                }
+                if n <= 8 {
+                    // Every permutation (Heap's algorithm):
+                    let mut c = [0_usize; 64];
+                    check(&perm[..n]);
+                    let mut i = 0;
+                    while i < n {
+                        if c[i] < i {
+                            if i % 2 == 0 {
+                                perm.swap(0, i);
+                            } else {
+                                perm.swap(c[i], i);
+                            }
+                            check(&perm[..n]);
+                            c[i] += 1;
+                            i = 0;
+                        } else {
+                            c[i] = 0;
+                            i += 1;
+                        }
+                    }
+                } else {
+                    let mut seed = 0x2545_f491_u64;
+                    for _ in 0..1000 {
+                        for i in (1..n).rev() {
+                            seed = seed.wrapping_mul(6_364_136_223_846_793_005)
+                                .wrapping_add(1);
+                            perm.swap(i, (seed >> 33) as usize % (i + 1));
+                        }
+                        check(&perm[..n]);
+                    }
+                }
+
+                // merge_sorted:
+                let mut a = $tn::splat(0 as $elem);
+                let mut b = $tn::splat(0 as $elem);
+                for i in 0..n {
+                    a = a.replace(i as u32, (2 * i) as $elem);
+                    b = b.replace(i as u32, (2 * i + 1) as $elem);
+                }
+                let (lo, hi) = $tn::merge_sorted(a, b);
+                for i in 0..n {
+                    assert_eq!(lo.extract(i as u32), i as $elem);
+                    assert_eq!(hi.extract(i as u32), (n + i) as $elem);
+                }
+                let (lo, hi) = $tn::merge_sorted(b, b);
+                for i in 0..n {
+                    let e = b.extract((i / 2) as u32);
+                    assert_eq!(lo.extract(i as u32), e);
+                    assert_eq!(
+                        hi.extract(i as u32),
+                        b.extract(((n + i) / 2) as u32)
+                    );
+                }
+
+                // sort_slice:
+                let mut seed = 0x9e37_79b9_u64;
+                for len in 0..65 {
+                    let mut x = [0 as $elem; 64];
+                    for v in &mut x[..len] {
+                        seed = seed.wrapping_mul(6_364_136_223_846_793_005)
+                            .wrapping_add(1);
+                        *v = ((seed >> 33) % 100) as $elem;
+                    }
+                    let mut e = x;
+                    // insertion sort as the reference:
+                    for i in 1..len {
+                        let mut j = i;
+                        while j > 0 && e[j] < e[j - 1] {
+                            e.swap(j, j - 1);
+                            j -= 1;
+                        }
+                    }
+                    $tn::sort_slice(&mut x[..len]);
+                    assert_eq!(&x[..len], &e[..len]);
+                }
             }
-            {  // or_assign
-                let mut v = z;
-                v |= o;
-                assert_eq!(v, o);
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_byte_shifts {
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let x = 0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100_u128;
+                let n = $tn::len() as u32;
+                let mut v = $tn::splat(0);
+                for i in 0..n {
+                    v = v.replace(i, (x ^ (i as u128) << 120) as $elem);
+                }
+                for b in 0..20 {
+                    let l = v.shl_bytes(b);
+                    let r = v.shr_bytes(b);
+                    for i in 0..n {
+                        let e = v.extract(i) as u128;
+                        let (el, er) = if b >= 16 {
+                            (0, 0)
+                        } else {
+                            (e << (8 * b), e >> (8 * b))
+                        };
+                        assert_eq!(l.extract(i), el as $elem);
+                        assert_eq!(r.extract(i), er as $elem);
+                    }
+                }
+                assert_eq!(v.shl_bytes(0), v);
+                assert_eq!(v.shr_bytes(0), v);
             }
-            {  // xor_assign
-                let mut v = z;
-                v ^= o;
-                assert_eq!(v, o);
+        )+
+    }
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_byte_order {
+    ($(($tn:ident, $elem:ident, $bytes:ident)),+) => {
+        $(
+            {
+                let n = $bytes::len() as u32;
+                let mut b = $bytes::splat(0);
+                let mut raw = [0_u8; 72];
+                for i in 0..n {
+                    b = b.replace(i, (i * 17 + 3) as u8);
+                    raw[i as usize + 5] = (i * 17 + 3) as u8;
+                }
+                let native = $tn::from(b);
+                let be = $tn::from_be_bytes(b);
+                let le = $tn::from_le_bytes(b);
+                let swapped = native.swap_bytes();
+                for i in 0..$tn::len() as u32 {
+                    let x = native.extract(i);
+                    assert_eq!(be.extract(i), $elem::from_be(x));
+                    assert_eq!(le.extract(i), $elem::from_le(x));
+                    assert_eq!(swapped.extract(i), x.swap_bytes());
+                }
+                assert_eq!(be.to_be_bytes(), b);
+                assert_eq!(le.to_le_bytes(), b);
+                assert_eq!(swapped.swap_bytes(), native);
+                assert_eq!($tn::load_be(&raw, 5), be);
+                assert_eq!($tn::load_le(&raw, 5), le);
             }
-        }
-    };
+        )+
+    }
 }
 
 #[cfg(test)]
 #[macro_export]
-macro_rules! test_ops_si {
-        ($($tn:ident),+) => {
-            $(
-                test_arithmetic_!($tn, 0, 1, 2, 4);
-                test_neg_!($tn, 0, 1, 2, 4);
-                test_bit_arithmetic_!($tn);
-            )+
-        };
+macro_rules! test_complex {
+    ($(($tn:ident, $fty:ident, $elem:ident)),+) => {
+        $(
+            {
+                // Small integers keep every product and sum exact, so the
+                // fused and unfused paths agree with the scalar reference.
+                let n = $fty::len() as u32;
+                let mut a = $fty::splat(0.);
+                let mut b = $fty::splat(0.);
+                for i in 0..n {
+                    a = a.replace(i, (i as $elem) * 3. - 5.);
+                    b = b.replace(i, 7. - (i as $elem) * 2.);
+                }
+                let (ca, cb) = ($tn::from_interleaved(a),
+                                $tn::from_interleaved(b));
+                assert_eq!(ca.to_interleaved(), a);
+
+                let mul = (ca * cb).to_interleaved();
+                let conj = ca.conj().to_interleaved();
+                let mul_i = ca.mul_i().to_interleaved();
+                let add = (ca + cb).to_interleaved();
+                let sub = (ca - cb).to_interleaved();
+                for k in 0..n / 2 {
+                    let (re, im) = (2 * k, 2 * k + 1);
+                    let (x, y) = (a.extract(re), a.extract(im));
+                    let (u, v) = (b.extract(re), b.extract(im));
+                    assert_eq!(mul.extract(re), x * u - y * v);
+                    assert_eq!(mul.extract(im), x * v + y * u);
+                    assert_eq!(conj.extract(re), x);
+                    assert_eq!(conj.extract(im), -y);
+                    assert_eq!(mul_i.extract(re), -y);
+                    assert_eq!(mul_i.extract(im), x);
+                    assert_eq!(add.extract(re), x + u);
+                    assert_eq!(sub.extract(im), y - v);
+                }
+                assert_eq!(ca.mul_i().mul_i(),
+                           $tn::from_interleaved(a * $fty::splat(-1.)));
+            }
+        )+
     }
+}
 
 #[cfg(test)]
 #[macro_export]
-macro_rules! test_ops_ui {
-        ($($tn:ident),+) => {
-            $(
-                test_arithmetic_!($tn, 0, 1, 2, 4);
-                test_bit_arithmetic_!($tn);
-            )+
-        };
+macro_rules! test_f16 {
+    ($(($tn:ident, $bits:ident, $fty:ident)),+) => {
+        $(
+            {
+                use f16::{f16_to_f32, f32_to_f16};
+                use simd::RoundingMode;
+
+                let modes = [
+                    RoundingMode::Nearest,
+                    RoundingMode::Down,
+                    RoundingMode::Up,
+                    RoundingMode::TowardZero,
+                ];
+                let fbits = |x: f32| -> u32 {
+                    unsafe { ::core::mem::transmute(x) }
+                };
+                let n = $bits::len() as u32;
+                let mut h = 0_u32;
+                while h < 0x1_0000 {
+                    let mut b = $bits::splat(0);
+                    let mut mid = $fty::splat(0.);
+                    for i in 0..n {
+                        let x = (h + i) as u16;
+                        let y = x.wrapping_add(1);
+                        let m = (f64::from(f16_to_f32(x))
+                                 + f64::from(f16_to_f32(y))) / 2.;
+                        b = b.replace(i, x);
+                        mid = mid.replace(i, m as f32);
+                    }
+                    let f = $tn::from_bits(b).to_f32();
+                    for i in 0..n {
+                        let e = f16_to_f32((h + i) as u16);
+                        assert_eq!(fbits(f.extract(i)), fbits(e));
+                    }
+                    for &mode in modes.iter() {
+                        for x in [f, mid].iter() {
+                            let r = $tn::from_f32(*x, mode).to_bits();
+                            for i in 0..n {
+                                let e = f32_to_f16(x.extract(i), mode);
+                                assert_eq!(r.extract(i), e);
+                            }
+                        }
+                    }
+                    h += n;
+                }
+            }
+        )+
     }
+}
 
 #[cfg(test)]
 #[macro_export]
-macro_rules! test_ops_f {
-        ($($tn:ident),+)  => {
-            $(
-                test_arithmetic_!($tn, 0., 1., 2., 4.);
-                test_neg_!($tn, 0., 1., 2., 4.);
-            )+
-        };
+macro_rules! test_transpose {
+    ($f:ident, $n:expr, $(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let mut m = [[0 as $elem; $n]; $n];
+                for (i, row) in m.iter_mut().enumerate() {
+                    for (j, x) in row.iter_mut().enumerate() {
+                        *x = (i * $n + j + 1) as $elem;
+                    }
+                }
+                let mut t = [[0 as $elem; $n]; $n];
+                for i in 0..$n {
+                    for j in 0..$n {
+                        t[j][i] = m[i][j];
+                    }
+                }
+
+                let mut rows = [$tn::splat(0 as $elem); $n];
+                for (r, x) in rows.iter_mut().zip(m.iter()) {
+                    *r = $tn::load(x, 0);
+                }
+                let r = $tn::$f(rows);
+                for (r, x) in r.iter().zip(t.iter()) {
+                    assert_eq!(&r.as_array()[..], &x[..]);
+                }
+                let r = $tn::$f(r);
+                assert_eq!(&r[..], &rows[..]);
+            }
+        )+
     }
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_array_traits {
+    ($(($tn:ident, $elem:ident, $nelems:expr)),+) => {
+        $(
+            {
+                let mut a = [0 as $elem; $nelems];
+                for (i, x) in a.iter_mut().enumerate() {
+                    *x = (i + 1) as $elem;
+                }
+                let v = $tn::from(a);
+                assert_eq!(&v.as_array()[..], &a[..]);
+                let b: [$elem; $nelems] = v.into();
+                assert_eq!(&b[..], &a[..]);
+                assert_eq!($tn::default(), $tn::splat(0 as $elem));
+
+                let mut w = v;
+                w.as_mut_array()[0] = 42 as $elem;
+                assert_eq!(w.extract(0), 42 as $elem);
+                for x in &mut w {
+                    *x = *x + (1 as $elem);
+                }
+                for (i, x) in (&w).into_iter().enumerate().skip(1) {
+                    assert_eq!(*x, (i + 2) as $elem);
+                }
+
+                let c: $tn = a.iter().cloned().collect();
+                assert_eq!(c, v);
+
+                let vs = [v, $tn::splat(1 as $elem), $tn::splat(2 as $elem)];
+                let s: $tn = vs.iter().sum();
+                assert_eq!(s, v + $tn::splat(3 as $elem));
+                let s: $tn = vs.iter().cloned().sum();
+                assert_eq!(s, v + $tn::splat(3 as $elem));
+                let p: $tn = vs.iter().product();
+                assert_eq!(p, v * $tn::splat(2 as $elem));
+                let p: $tn = vs.iter().cloned().product();
+                assert_eq!(p, v * $tn::splat(2 as $elem));
+                let empty: [$tn; 0] = [];
+                let e: $tn = empty.iter().sum();
+                assert_eq!(e, $tn::splat(0 as $elem));
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_from_iter_len {
+    ($(($tn:ident, $elem:ident, $nelems:expr)),+) => {
+        $(
+            {
+                use std::iter::FromIterator;
+                use std::panic;
+                let short = panic::catch_unwind(|| {
+                    $tn::from_iter((1..$nelems).map(|x| x as $elem))
+                });
+                assert!(short.is_err());
+                let long = panic::catch_unwind(|| {
+                    $tn::from_iter((0..$nelems + 1).map(|x| x as $elem))
+                });
+                assert!(long.is_err());
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_hash {
+    ($($tn:ident),+) => {
+        $(
+            {
+                use std::collections::HashSet;
+                let mut set = HashSet::new();
+                for i in 0..10 {
+                    set.insert($tn::splat(i));
+                }
+                set.insert($tn::splat(3));
+                assert_eq!(set.len(), 10);
+                assert!(set.contains(&$tn::splat(7)));
+                assert!(!set.contains(&$tn::splat(10)));
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_lane_fmt_ {
+    ($v:expr, $fmt:tt) => {
+        {
+            use std::string::String;
+            let v = $v;
+            let mut e = String::from("[");
+            for (i, x) in v.as_array().iter().enumerate() {
+                if i > 0 {
+                    e.push_str(", ");
+                }
+                e.push_str(&format!($fmt, x));
+            }
+            e.push_str("]");
+            assert_eq!(format!($fmt, v), e);
+        }
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_float_fmt {
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let mut v = $tn::splat(0 as $elem);
+                for i in 0..$tn::len() as u32 {
+                    v = v.replace(i, i as $elem * -1.25);
+                }
+                test_lane_fmt_!(v, "{}");
+                test_lane_fmt_!(v, "{:.3}");
+                test_lane_fmt_!(v, "{:>9.1}");
+                test_lane_fmt_!(v, "{:+}");
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_integer_fmt {
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                let mut v = $tn::splat(0 as $elem);
+                for i in 0..$tn::len() as u32 {
+                    v = v.replace(i, (i * 29) as $elem);
+                }
+                test_lane_fmt_!(v, "{}");
+                test_lane_fmt_!(v, "{:5}");
+                test_lane_fmt_!(v, "{:*<6}");
+                test_lane_fmt_!(v, "{:x}");
+                test_lane_fmt_!(v, "{:#06x}");
+                test_lane_fmt_!(v, "{:X}");
+                test_lane_fmt_!(v, "{:o}");
+                test_lane_fmt_!(v, "{:#o}");
+                test_lane_fmt_!(v, "{:b}");
+                test_lane_fmt_!(v, "{:08b}");
+            }
+        )+
+    };
+}
+
+#[cfg(all(test, feature = "serde"))]
+#[macro_export]
+macro_rules! test_serde {
+    ($(($tn:ident, $elem:ident)),+) => {
+        $(
+            {
+                use std::vec::Vec;
+                use bincode;
+                use serde_json;
+
+                let mut v = $tn::splat(0 as $elem);
+                for i in 0..$tn::len() as u32 {
+                    v = v.replace(i, (i * 3 + 1) as $elem);
+                }
+
+                // Self-describing format:
+                let json = serde_json::to_string(&v).unwrap();
+                let lanes: Vec<$elem> = serde_json::from_str(&json).unwrap();
+                assert_eq!(&lanes[..], &v.as_array()[..]);
+                let w: $tn = serde_json::from_str(&json).unwrap();
+                assert_eq!(w, v);
+
+                let short = serde_json::to_string(&lanes[1..]).unwrap();
+                assert!(serde_json::from_str::<$tn>(&short).is_err());
+                let mut long = lanes.clone();
+                long.push(0 as $elem);
+                let long = serde_json::to_string(&long).unwrap();
+                assert!(serde_json::from_str::<$tn>(&long).is_err());
+
+                // Binary format:
+                let bytes = bincode::serialize(&v).unwrap();
+                assert_eq!(
+                    bytes.len(),
+                    $tn::len() as usize * ::core::mem::size_of::<$elem>()
+                );
+                let w: $tn = bincode::deserialize(&bytes).unwrap();
+                assert_eq!(w, v);
+                let r = bincode::deserialize::<$tn>(&bytes[1..]);
+                assert!(r.is_err());
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_serde_via {
+    ($(($tn:ident, $repr:ident, $elem:ident, $to:ident, $from:ident)),+) => {
+        $(
+            {
+                use serde_json;
+
+                let mut r = $repr::splat(0 as $elem);
+                for i in 0..$repr::len() as u32 {
+                    r = r.replace(i, (i * 3 + 1) as $elem);
+                }
+                let v = $tn::$from(r);
+                let json = serde_json::to_string(&v).unwrap();
+                assert_eq!(json, serde_json::to_string(&r).unwrap());
+                let w: $tn = serde_json::from_str(&json).unwrap();
+                assert_eq!(w.$to(), r);
+            }
+        )+
+    };
+}
@@ -0,0 +1,306 @@
+//! Identification of x86 CPUs: vendor, brand string and signature.
+
+use core::{fmt, slice, str};
+
+use vendor::CpuidResult;
+
+use super::{CpuidQuery, Host};
+
+/// The vendor of an x86 CPU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuVendor {
+    /// `GenuineIntel`
+    Intel,
+    /// `AuthenticAMD`
+    Amd,
+    /// Any other vendor, see `CpuInfo::vendor_id`.
+    Other,
+}
+
+/// Identification of the host x86 CPU.
+///
+/// ```ignore
+/// let info = CpuInfo::new();
+/// if info.vendor() == CpuVendor::Intel && info.family() == 6 {
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CpuInfo {
+    /// EBX, EDX and ECX of leaf 0.
+    vendor_id: [u32; 3],
+    /// EAX, EBX, ECX and EDX of leaves 0x8000_0002 to 0x8000_0004.
+    brand: [u32; 12],
+    /// EAX of leaf 1.
+    signature: u32,
+}
+
+impl CpuInfo {
+    /// Identifies the host CPU.
+    ///
+    /// If the CPU does not support the `cpuid` instruction, the vendor ID
+    /// is empty and the family, model and stepping are zero.
+    #[cfg_attr(feature = "cargo-clippy", allow(new_without_default))]
+    pub fn new() -> CpuInfo {
+        match Host::new() {
+            Some(host) => CpuInfo::from_query(host),
+            None => CpuInfo::empty(),
+        }
+    }
+
+    fn empty() -> CpuInfo {
+        CpuInfo {
+            vendor_id: [0; 3],
+            brand: [0; 12],
+            signature: 0,
+        }
+    }
+
+    /// Identifies the CPU from the `cpuid` values of `x`.
+    fn from_query<T: CpuidQuery>(mut x: T) -> CpuInfo {
+        let mut info = CpuInfo::empty();
+
+        // EAX = 0: the highest basic leaf, and the vendor ID in EBX, EDX
+        // and ECX (in that order):
+        let CpuidResult {
+            eax: max_basic_leaf,
+            ebx,
+            ecx,
+            edx,
+        } = x.cpuid(0, 0);
+        info.vendor_id = [ebx, edx, ecx];
+
+        // EAX = 1: the processor signature in EAX.
+        if max_basic_leaf >= 1 {
+            info.signature = x.cpuid(1, 0).eax;
+        }
+
+        // EAX = 0x8000_0002..0x8000_0004: the 48 bytes of the brand string.
+        let max_extended_leaf = x.cpuid(0x8000_0000_u32, 0).eax;
+        if max_extended_leaf >= 0x8000_0004_u32 {
+            for (i, leaf) in (0x8000_0002_u32..0x8000_0005_u32).enumerate() {
+                let CpuidResult { eax, ebx, ecx, edx } = x.cpuid(leaf, 0);
+                info.brand[4 * i..4 * i + 4]
+                    .copy_from_slice(&[eax, ebx, ecx, edx]);
+            }
+        }
+        info
+    }
+
+    /// The vendor of the CPU.
+    pub fn vendor(&self) -> CpuVendor {
+        match self.vendor_id() {
+            "GenuineIntel" => CpuVendor::Intel,
+            "AuthenticAMD" => CpuVendor::Amd,
+            _ => CpuVendor::Other,
+        }
+    }
+
+    /// The 12 character vendor ID, e.g. `GenuineIntel`, `AuthenticAMD` or
+    /// `CentaurHauls`.
+    pub fn vendor_id(&self) -> &str {
+        to_str(&self.vendor_id).trim_right_matches('\0')
+    }
+
+    /// The brand string, e.g. `Intel(R) Core(TM) i7-6700K CPU @ 4.00GHz`,
+    /// without its padding.
+    ///
+    /// Returns `None` if the CPU does not report a brand string.
+    pub fn brand_string(&self) -> Option<&str> {
+        let brand = to_str(&self.brand)
+            .trim_right_matches('\0')
+            .trim();
+        if brand.is_empty() {
+            None
+        } else {
+            Some(brand)
+        }
+    }
+
+    /// The family of the CPU.
+    ///
+    /// The extended family is only added to family `0xF`.
+    pub fn family(&self) -> u32 {
+        let family = (self.signature >> 8) & 0xf;
+        if family == 0xf {
+            family + ((self.signature >> 20) & 0xff)
+        } else {
+            family
+        }
+    }
+
+    /// The model of the CPU.
+    ///
+    /// The extended model is only used by families `0x6` and `0xF`.
+    pub fn model(&self) -> u32 {
+        let family = (self.signature >> 8) & 0xf;
+        let model = (self.signature >> 4) & 0xf;
+        if family == 0x6 || family == 0xf {
+            ((self.signature >> 12) & 0xf0) + model
+        } else {
+            model
+        }
+    }
+
+    /// The stepping of the CPU.
+    pub fn stepping(&self) -> u32 {
+        self.signature & 0xf
+    }
+}
+
+impl fmt::Debug for CpuInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CpuInfo")
+            .field("vendor_id", &self.vendor_id())
+            .field("brand_string", &self.brand_string())
+            .field("family", &self.family())
+            .field("model", &self.model())
+            .field("stepping", &self.stepping())
+            .finish()
+    }
+}
+
+/// Interprets the bytes of the registers, in memory order, as ASCII.
+/// Invalid UTF-8 reads as an empty string.
+fn to_str(registers: &[u32]) -> &str {
+    let bytes = unsafe {
+        slice::from_raw_parts(
+            registers.as_ptr() as *const u8,
+            4 * registers.len(),
+        )
+    };
+    str::from_utf8(bytes).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use runtime::dumps::{self, Dump};
+
+    use super::*;
+
+    #[test]
+    fn intel_skylake() {
+        let info = CpuInfo::from_query(dumps::SKYLAKE);
+        assert_eq!(info.vendor(), CpuVendor::Intel);
+        assert_eq!(info.vendor_id(), "GenuineIntel");
+        assert_eq!(
+            info.brand_string(),
+            Some("Intel(R) Core(TM) i7-6700K CPU @ 4.00GHz")
+        );
+        assert_eq!(info.family(), 6);
+        assert_eq!(info.model(), 0x5e);
+        assert_eq!(info.stepping(), 3);
+    }
+
+    #[test]
+    fn intel_skylake_x() {
+        let info = CpuInfo::from_query(dumps::SKYLAKE_X);
+        assert_eq!(info.vendor(), CpuVendor::Intel);
+        assert_eq!(info.brand_string(), None);
+        assert_eq!(info.family(), 6);
+        assert_eq!(info.model(), 0x55);
+        assert_eq!(info.stepping(), 4);
+    }
+
+    #[test]
+    fn intel_pentium4() {
+        // Pentium 4 (Northwood): family 0xF without an extended family.
+        // The brand string leaves are left out.
+        let info = CpuInfo::from_query(Dump {
+            leaves: &[
+                (0, 0, [0x2, 0x756e_6547, 0x6c65_746e, 0x4965_6e69]),
+                (1, 0, [0x0000_0f29, 0, 0, 0]),
+                (0x8000_0000, 0, [0x8000_0000, 0, 0, 0]),
+            ],
+            xcr0: 0,
+        });
+        assert_eq!(info.vendor(), CpuVendor::Intel);
+        assert_eq!(info.brand_string(), None);
+        assert_eq!(info.family(), 0xf);
+        assert_eq!(info.model(), 2);
+        assert_eq!(info.stepping(), 9);
+    }
+
+    #[test]
+    fn amd_zen() {
+        // The brand string has trailing spaces.
+        let info = CpuInfo::from_query(dumps::ZEN);
+        assert_eq!(info.vendor(), CpuVendor::Amd);
+        assert_eq!(info.vendor_id(), "AuthenticAMD");
+        assert_eq!(
+            info.brand_string(),
+            Some("AMD Ryzen 7 1700 Eight-Core Processor")
+        );
+        assert_eq!(info.family(), 0x17);
+        assert_eq!(info.model(), 1);
+        assert_eq!(info.stepping(), 1);
+    }
+
+    #[test]
+    fn amd_zen2() {
+        let info = CpuInfo::from_query(dumps::ZEN2);
+        assert_eq!(info.vendor(), CpuVendor::Amd);
+        assert_eq!(info.family(), 0x17);
+        assert_eq!(info.model(), 0x71);
+        assert_eq!(info.stepping(), 0);
+    }
+
+    #[test]
+    fn amd_zen3() {
+        // Ryzen 9 5950X: both the extended family and the extended model.
+        let info = CpuInfo::from_query(Dump {
+            leaves: &[
+                (0, 0, [0x10, 0x6874_7541, 0x444d_4163, 0x6974_6e65]),
+                (1, 0, [0x00a2_0f10, 0, 0, 0]),
+            ],
+            xcr0: 0,
+        });
+        assert_eq!(info.vendor(), CpuVendor::Amd);
+        assert_eq!(info.family(), 0x19);
+        assert_eq!(info.model(), 0x21);
+        assert_eq!(info.stepping(), 0);
+    }
+
+    #[test]
+    fn via_nano() {
+        let info = CpuInfo::from_query(Dump {
+            leaves: &[
+                (0, 0, [0xa, 0x746e_6543, 0x736c_7561, 0x4872_7561]),
+                (1, 0, [0x0000_06f2, 0, 0, 0]),
+                (0x8000_0000, 0, [0x8000_0008, 0, 0, 0]),
+                (
+                    0x8000_0002,
+                    0,
+                    [0x2041_4956, 0x6f6e_614e, 0x6f72_7020, 0x7373_6563],
+                ),
+                (
+                    0x8000_0003,
+                    0,
+                    [0x5520_726f, 0x3035_3232, 0x2e31_2820, 0x7a48_4736],
+                ),
+                (0x8000_0004, 0, [0x7061_4320, 0x656c_6261, 0x29, 0]),
+            ],
+            xcr0: 0,
+        });
+        assert_eq!(info.vendor(), CpuVendor::Other);
+        assert_eq!(info.vendor_id(), "CentaurHauls");
+        assert_eq!(
+            info.brand_string(),
+            Some("VIA Nano processor U2250 (1.6GHz Capable)")
+        );
+        assert_eq!(info.family(), 6);
+        assert_eq!(info.model(), 0xf);
+        assert_eq!(info.stepping(), 2);
+    }
+
+    #[test]
+    fn host() {
+        let info = CpuInfo::new();
+        println!("{:?}", info);
+        assert_eq!(
+            info.vendor() == CpuVendor::Other,
+            info.vendor_id() != "GenuineIntel"
+                && info.vendor_id() != "AuthenticAMD"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Recorded `cpuid` and XCR0 values of x86 CPUs, used to test the
+//! detection without depending on the host.
+
+use vendor::CpuidResult;
+
+use super::CpuidQuery;
+
+/// Recorded `(leaf, sub_leaf, [eax, ebx, ecx, edx])` `cpuid` results and
+/// XCR0 value. Unlisted leaves read as zero.
+#[derive(Clone, Copy)]
+pub struct Dump<'a> {
+    pub leaves: &'a [(u32, u32, [u32; 4])],
+    pub xcr0: u64,
+}
+
+impl<'a> CpuidQuery for Dump<'a> {
+    fn cpuid(&mut self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        let r = self.leaves
+            .iter()
+            .find(|l| l.0 == leaf && l.1 == sub_leaf)
+            .map_or([0; 4], |l| l.2);
+        CpuidResult {
+            eax: r[0],
+            ebx: r[1],
+            ecx: r[2],
+            edx: r[3],
+        }
+    }
+
+    fn xcr0(&mut self) -> u64 {
+        self.xcr0
+    }
+}
+
+// The vendor ID of leaf 0 is stored in EBX, EDX and ECX, in that order:
+// `GenuineIntel` reads `[0x756e_6547, 0x6c65_746e, 0x4965_6e69]` and
+// `AuthenticAMD` reads `[0x6874_7541, 0x444d_4163, 0x6974_6e65]` in
+// EBX, ECX and EDX.
+
+/// Core i7-6700K (Skylake): 4 cores, 8 threads.
+pub const SKYLAKE: Dump<'static> = Dump {
+    leaves: &[
+        (0, 0, [0x16, 0x756e_6547, 0x6c65_746e, 0x4965_6e69]),
+        (1, 0, [0x0005_06e3, 0x0010_0800, 0x7ffa_fbbf, 0xbfeb_fbff]),
+        (4, 0, [0x0c00_4121, 0x01c0_003f, 0x0000_003f, 0]),
+        (4, 1, [0x0c00_4122, 0x01c0_003f, 0x0000_003f, 0]),
+        (4, 2, [0x0c00_4143, 0x00c0_003f, 0x0000_03ff, 0]),
+        (4, 3, [0x0c03_c163, 0x03c0_003f, 0x0000_1fff, 0x6]),
+        (7, 0, [0, 0x029c_6fbf, 0, 0x0c00_0000]),
+        (0xb, 0, [0x1, 0x2, 0x100, 0x3]),
+        (0xb, 1, [0x4, 0x8, 0x201, 0x3]),
+        (0xb, 2, [0, 0, 0x2, 0x3]),
+        (0xd, 1, [0xf, 0, 0, 0]),
+        (0x8000_0000, 0, [0x8000_0008, 0, 0, 0]),
+        (0x8000_0001, 0, [0, 0, 0x121, 0x2c10_0800]),
+        (
+            0x8000_0002,
+            0,
+            [0x6574_6e49, 0x2952_286c, 0x726f_4320, 0x4d54_2865],
+        ),
+        (
+            0x8000_0003,
+            0,
+            [0x3769_2029, 0x3037_362d, 0x4320_4b30, 0x4020_5550],
+        ),
+        (0x8000_0004, 0, [0x302e_3420, 0x7a48_4730, 0, 0]),
+    ],
+    xcr0: 0x7,
+};
+
+/// Core i9-7900X (Skylake-X): AVX-512 F, CD, BW, DQ and VL.
+pub const SKYLAKE_X: Dump<'static> = Dump {
+    leaves: &[
+        (0, 0, [0x16, 0x756e_6547, 0x6c65_746e, 0x4965_6e69]),
+        (1, 0, [0x0005_0654, 0x0010_0800, 0x7ffe_fbff, 0xbfeb_fbff]),
+        (7, 0, [0, 0xd39f_dfbb, 0x0000_0018, 0xc000_0000]),
+        (0xd, 1, [0xf, 0, 0, 0]),
+        (0x8000_0000, 0, [0x8000_0008, 0, 0, 0]),
+        (0x8000_0001, 0, [0, 0, 0x121, 0x2c10_0800]),
+    ],
+    xcr0: 0xff,
+};
+
+/// Skylake-X in a virtual machine whose hypervisor reports AVX-512 in
+/// `cpuid` but does not enable the AVX state in XCR0.
+pub const VM_WITHOUT_AVX: Dump<'static> = Dump {
+    leaves: &[
+        (0, 0, [0x16, 0x756e_6547, 0x6c65_746e, 0x4965_6e69]),
+        (1, 0, [0x0005_0654, 0x0010_0800, 0xfffe_fbff, 0xbfeb_fbff]),
+        (7, 0, [0, 0xd39f_dfbb, 0x0000_0018, 0xc000_0000]),
+        (0xd, 1, [0xf, 0, 0, 0]),
+        (0x8000_0000, 0, [0x8000_0008, 0, 0, 0]),
+        (0x8000_0001, 0, [0, 0, 0x121, 0x2c10_0800]),
+    ],
+    xcr0: 0x3,
+};
+
+/// Ryzen 7 1700 (Zen): 8 cores, 16 threads, in two core complexes.
+pub const ZEN: Dump<'static> = Dump {
+    leaves: &[
+        (0, 0, [0xd, 0x6874_7541, 0x444d_4163, 0x6974_6e65]),
+        (1, 0, [0x0080_0f11, 0x0010_0800, 0x7ed8_320b, 0x178b_fbff]),
+        (7, 0, [0, 0x209c_01a9, 0, 0]),
+        (0xd, 1, [0xf, 0, 0, 0]),
+        (0x8000_0000, 0, [0x8000_001f, 0, 0, 0]),
+        (0x8000_0001, 0, [0, 0, 0x35c2_33ff, 0x2fd3_fbff]),
+        (
+            0x8000_0002,
+            0,
+            [0x2044_4d41, 0x657a_7952, 0x2037_206e, 0x3030_3731],
+        ),
+        (
+            0x8000_0003,
+            0,
+            [0x6769_4520, 0x432d_7468, 0x2065_726f, 0x636f_7250],
+        ),
+        (
+            0x8000_0004,
+            0,
+            [0x6f73_7365, 0x2020_2072, 0x2020_2020, 0x0020_2020],
+        ),
+        (0x8000_001d, 0, [0x0000_4121, 0x01c0_003f, 0x0000_003f, 0]),
+        (0x8000_001d, 1, [0x0000_4122, 0x00c0_003f, 0x0000_00ff, 0]),
+        (0x8000_001d, 2, [0x0000_4143, 0x01c0_003f, 0x0000_03ff, 0x2]),
+        (0x8000_001d, 3, [0x0001_c163, 0x03c0_003f, 0x0000_1fff, 0x1]),
+    ],
+    xcr0: 0x7,
+};
+
+/// Ryzen 7 3700X (Zen 2).
+pub const ZEN2: Dump<'static> = Dump {
+    leaves: &[
+        (0, 0, [0x10, 0x6874_7541, 0x444d_4163, 0x6974_6e65]),
+        (1, 0, [0x0087_0f10, 0x0010_0800, 0x7ed8_320b, 0x178b_fbff]),
+        (7, 0, [0, 0x219c_91a9, 0x0040_0004, 0]),
+        (0xd, 1, [0xf, 0, 0, 0]),
+        (0x8000_0000, 0, [0x8000_0020, 0, 0, 0]),
+        (0x8000_0001, 0, [0, 0, 0x75c2_37ff, 0x2fd3_fbff]),
+    ],
+    xcr0: 0x7,
+};
+
+/// Pentium J4205 (Goldmont Atom): SSE4.2, AES and SHA, but no AVX.
+pub const GOLDMONT: Dump<'static> = Dump {
+    leaves: &[
+        (0, 0, [0x15, 0x756e_6547, 0x6c65_746e, 0x4965_6e69]),
+        (1, 0, [0x0005_06c9, 0x0010_0800, 0x4ff8_ebbf, 0xbfeb_fbff]),
+        (7, 0, [0, 0x2294_4683, 0, 0x0c00_0000]),
+        (0xd, 1, [0xf, 0, 0, 0]),
+        (0x8000_0000, 0, [0x8000_0008, 0, 0, 0]),
+        (0x8000_0001, 0, [0, 0, 0x101, 0x2c10_0800]),
+    ],
+    xcr0: 0x1b,
+};
@@ -0,0 +1,231 @@
+//! x86-64 microarchitecture levels.
+//!
+//! The levels of the x86-64 psABI group the features that distributions
+//! and compilers target together: `x86-64-v2` roughly matches Nehalem,
+//! `x86-64-v3` Haswell, and `x86-64-v4` Skylake-X.
+
+use super::cache::{self, Initializer};
+use super::detect_features;
+use super::x86::__Feature;
+
+/// An x86-64 microarchitecture level.
+///
+/// Levels are ordered: a CPU supporting a level supports the lower ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MicroarchLevel {
+    /// `x86-64`: the baseline, SSE2.
+    V1,
+    /// `x86-64-v2`: CMPXCHG16B, LAHF/SAHF, POPCNT, SSE3 to SSE4.2.
+    V2,
+    /// `x86-64-v3`: AVX, AVX2, BMI1, BMI2, F16C, FMA, LZCNT, MOVBE and
+    /// XSAVE.
+    V3,
+    /// `x86-64-v4`: AVX-512 F, BW, CD, DQ and VL.
+    V4,
+}
+
+impl MicroarchLevel {
+    /// The name of the level, e.g. `x86-64-v2`.
+    pub fn name(self) -> &'static str {
+        match self {
+            MicroarchLevel::V1 => "x86-64-v1",
+            MicroarchLevel::V2 => "x86-64-v2",
+            MicroarchLevel::V3 => "x86-64-v3",
+            MicroarchLevel::V4 => "x86-64-v4",
+        }
+    }
+}
+
+/// The features each level adds to the previous one.
+///
+/// CMOV, CX8, FPU and SYSCALL, also required by `x86-64-v1`, are not
+/// detected: every CPU with SSE2 has them.
+const LEVELS: &[(MicroarchLevel, &[__Feature])] = &[
+    (
+        MicroarchLevel::V1,
+        &[__Feature::fxsr, __Feature::mmx, __Feature::sse, __Feature::sse2],
+    ),
+    (
+        MicroarchLevel::V2,
+        &[
+            __Feature::cmpxchg16b,
+            __Feature::lahfsahf,
+            __Feature::popcnt,
+            __Feature::sse3,
+            __Feature::sse4_1,
+            __Feature::sse4_2,
+            __Feature::ssse3,
+        ],
+    ),
+    (
+        MicroarchLevel::V3,
+        &[
+            __Feature::avx,
+            __Feature::avx2,
+            __Feature::bmi,
+            __Feature::bmi2,
+            __Feature::f16c,
+            __Feature::fma,
+            __Feature::lzcnt,
+            __Feature::movbe,
+            __Feature::xsave,
+        ],
+    ),
+    (
+        MicroarchLevel::V4,
+        &[
+            __Feature::avx512f,
+            __Feature::avx512bw,
+            __Feature::avx512cd,
+            __Feature::avx512dq,
+            __Feature::avx512vl,
+        ],
+    ),
+];
+
+/// The features of `level` and of the levels below it.
+fn level_mask(level: MicroarchLevel) -> Initializer {
+    let mut mask = Initializer::default();
+    for &(_, features) in LEVELS.iter().take_while(|&&(l, _)| l <= level) {
+        for &f in features {
+            mask.set(f as u32);
+        }
+    }
+    mask
+}
+
+/// Returns the highest x86-64 microarchitecture level supported by the
+/// host CPU, or `None` if it does not even support SSE2.
+///
+/// Like `is_detected`, this always queries the run-time cache.
+pub fn microarch_level() -> Option<MicroarchLevel> {
+    LEVELS
+        .iter()
+        .rev()
+        .map(|&(l, _)| l)
+        .find(|&l| __unstable_detect_level(l))
+}
+
+/// Performs run-time detection of a microarchitecture level.
+#[doc(hidden)]
+pub fn __unstable_detect_level(level: MicroarchLevel) -> bool {
+    cache::test_all(&level_mask(level), detect_features)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use runtime::cache::Cache;
+    use runtime::dumps;
+    use runtime::x86::{detect_features_impl, FEATURE_NAMES};
+
+    use super::*;
+
+    /// The highest level whose features are all in `value`, if any.
+    fn level_of(value: &Initializer) -> Option<MicroarchLevel> {
+        LEVELS.iter().rev().map(|&(l, _)| l).find(|&l| {
+            let mut missing = level_mask(l);
+            missing.remove(value);
+            missing == Initializer::default()
+        })
+    }
+
+    /// The level of a set of feature names.
+    fn level(names: &[&str]) -> Option<MicroarchLevel> {
+        let mut value = Initializer::default();
+        for &name in names {
+            let bit = FEATURE_NAMES.iter().position(|&n| n == name).unwrap();
+            value.set(bit as u32);
+        }
+        level_of(&value)
+    }
+
+    const V1: &[&str] = &["fxsr", "mmx", "sse", "sse2"];
+    const V2: &[&str] = &[
+        "cmpxchg16b", "lahfsahf", "popcnt", "sse3", "sse4.1", "sse4.2",
+        "ssse3",
+    ];
+    const V3: &[&str] = &[
+        "avx", "avx2", "bmi", "bmi2", "f16c", "fma", "lzcnt", "movbe",
+        "xsave",
+    ];
+    const V4: &[&str] =
+        &["avx512f", "avx512bw", "avx512cd", "avx512dq", "avx512vl"];
+
+    fn concat(sets: &[&[&'static str]]) -> Vec<&'static str> {
+        sets.iter().flat_map(|s| s.iter().cloned()).collect()
+    }
+
+    #[test]
+    fn levels() {
+        let table = [
+            (concat(&[]), None),
+            (concat(&[&["sse", "sse2"]]), None),
+            (concat(&[V1]), Some(MicroarchLevel::V1)),
+            (concat(&[V1, V2]), Some(MicroarchLevel::V2)),
+            (concat(&[V1, V2, V3]), Some(MicroarchLevel::V3)),
+            (concat(&[V1, V2, V3, V4]), Some(MicroarchLevel::V4)),
+            // A missing feature caps the level, whatever is above it.
+            (concat(&[V1, &V2[1..], V3, V4]), Some(MicroarchLevel::V1)),
+            (concat(&[V1, V2, &V3[..8], V4]), Some(MicroarchLevel::V2)),
+            (concat(&[V1, V2, V3, &V4[1..]]), Some(MicroarchLevel::V3)),
+            (concat(&[V2, V3, V4]), None),
+        ];
+        for &(ref names, expected) in &table {
+            assert_eq!(level(names), expected, "{:?}", names);
+        }
+    }
+
+    #[test]
+    fn recorded() {
+        let table = [
+            (dumps::SKYLAKE, MicroarchLevel::V3),
+            (dumps::SKYLAKE_X, MicroarchLevel::V4),
+            (dumps::VM_WITHOUT_AVX, MicroarchLevel::V2),
+            (dumps::ZEN, MicroarchLevel::V3),
+            (dumps::ZEN2, MicroarchLevel::V3),
+            (dumps::GOLDMONT, MicroarchLevel::V2),
+        ];
+        for &(dump, expected) in &table {
+            let value = detect_features_impl(dump);
+            assert_eq!(level_of(&value), Some(expected));
+            // One read of the cache per level.
+            let cache = Cache::new();
+            let detect = || detect_features_impl(dump);
+            for &(l, _) in LEVELS {
+                let detected = cache.test_all(&level_mask(l), &detect);
+                assert_eq!(detected, l <= expected);
+            }
+        }
+    }
+
+    #[test]
+    fn masks() {
+        let v1 = level_mask(MicroarchLevel::V1);
+        let v2 = level_mask(MicroarchLevel::V2);
+        assert!(v1.test(__Feature::sse2 as u32));
+        assert!(!v1.test(__Feature::sse4_2 as u32));
+        assert!(v2.test(__Feature::sse2 as u32));
+        assert!(v2.test(__Feature::sse4_2 as u32));
+        assert!(!v2.test(__Feature::avx as u32));
+        assert!(level_mask(MicroarchLevel::V4).test(__Feature::fxsr as u32));
+    }
+
+    #[test]
+    fn host() {
+        let level = microarch_level();
+        println!("{:?}", level.map(MicroarchLevel::name));
+        assert_eq!(
+            cfg_feature_enabled!("x86-64-v2"),
+            level >= Some(MicroarchLevel::V2)
+        );
+        assert_eq!(
+            cfg_feature_enabled!("x86-64-v3"),
+            level >= Some(MicroarchLevel::V3)
+        );
+        if cfg!(target_arch = "x86_64") {
+            assert!(cfg_feature_enabled!("x86-64-v1"));
+        }
+    }
+}
@@ -1,6 +1,11 @@
 //! Run-time feature detection
 mod cache;
 mod bit;
+mod feature;
+mod cpuinfo;
+mod topology;
+#[cfg(test)]
+mod dumps;
 
 #[macro_use]
 mod macros;
@@ -8,10 +13,87 @@ mod macros;
 #[macro_use]
 mod x86;
 pub use self::x86::__Feature;
-use self::x86::detect_features;
+
+mod level;
+pub use self::level::{microarch_level, MicroarchLevel,
+                      __unstable_detect_level};
+use self::x86::{detect_features, FEATURE_ALIASES, FEATURE_NAMES};
+
+pub use self::feature::{detected_features, is_detected, override_features,
+                        DetectedFeatures, Feature, ParseFeatureError};
+pub use self::cpuinfo::{CpuInfo, CpuVendor};
+pub use self::topology::{CacheDescriptor, CacheInfo, CacheType, Topology,
+                         TopologyLevel, TopologyLevelType};
+
+use vendor::CpuidResult;
+
+/// A source of `cpuid` and XCR0 values: the host CPU, or a recorded dump
+/// in the tests.
+pub trait CpuidQuery {
+    /// The result of `cpuid` for `leaf` (`EAX`) and `sub_leaf` (`ECX`).
+    fn cpuid(&mut self, leaf: u32, sub_leaf: u32) -> CpuidResult;
+    /// The value of XCR0. Only queried if `cpuid` reports `xsave`
+    /// support.
+    fn xcr0(&mut self) -> u64;
+}
+
+/// The host CPU, which is known to support the `cpuid` instruction.
+struct Host(());
+
+impl Host {
+    /// Returns `None` if the host CPU does not support `cpuid`.
+    fn new() -> Option<Host> {
+        if ::vendor::has_cpuid() {
+            Some(Host(()))
+        } else {
+            None
+        }
+    }
+}
+
+impl CpuidQuery for Host {
+    fn cpuid(&mut self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        // This is safe because the CPU has `cpuid` support.
+        unsafe { ::vendor::__cpuid_count(leaf, sub_leaf) }
+    }
+
+    fn xcr0(&mut self) -> u64 {
+        // This is safe because the CPU supports `xsave`.
+        unsafe { ::vendor::_xgetbv(0) }
+    }
+}
 
 /// Performs run-time feature detection.
 #[doc(hidden)]
 pub fn __unstable_detect_feature(x: __Feature) -> bool {
     cache::test(x as u32, detect_features)
 }
+
+/// Performs run-time detection of all the features of `bits`, skipping the
+/// `None` entries, which are enabled at compile-time.
+#[doc(hidden)]
+pub fn __unstable_detect_all(bits: &[Option<u32>]) -> bool {
+    let mut mask = cache::Initializer::default();
+    for &bit in bits.iter().filter_map(|b| b.as_ref()) {
+        mask.set(bit);
+    }
+    cache::test_all(&mask, detect_features)
+}
+
+/// Registers `hook`, which reports the features to disable when the cache
+/// is filled. `stdsimd` registers the `STDSIMD_DISABLE` environment
+/// variable this way.
+#[doc(hidden)]
+pub fn __unstable_set_disable_hook(hook: fn(&mut FnMut(&str))) {
+    cache::set_disable_hook(hook)
+}
+
+/// Performs run-time detection of any of the features of `bits`.
+#[doc(hidden)]
+pub fn __unstable_detect_any(bits: &[u32]) -> bool {
+    let mut mask = cache::Initializer::default();
+    for &bit in bits {
+        mask.set(bit);
+    }
+    cache::test_any(&mask, detect_features)
+}
@@ -0,0 +1,518 @@
+//! Cache and core/thread topology of x86 CPUs.
+//!
+//! The caches are enumerated by the deterministic cache parameters leaf:
+//! leaf 4 on Intel, leaf 0x8000_001D on AMD and Hygon. The topology is
+//! enumerated by the V2 extended topology leaf 0x1F if available, or by leaf
+//! 0xB.
+
+use core::slice;
+
+use vendor::CpuidResult;
+
+use super::{CpuidQuery, Host};
+
+/// Maximum number of cache descriptors of a `CacheInfo`.
+const MAX_CACHES: usize = 8;
+
+/// Maximum number of levels of a `Topology`.
+const MAX_LEVELS: usize = 8;
+
+/// The type of a cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheType {
+    /// Data cache
+    Data,
+    /// Instruction cache
+    Instruction,
+    /// Unified (data and instruction) cache
+    Unified,
+}
+
+/// The parameters of a cache, as reported by one sub-leaf of leaf 4 or
+/// 0x8000_001D.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheDescriptor {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+impl CacheDescriptor {
+    /// The type of the cache, or `None` once past the last cache.
+    fn try_cache_type(&self) -> Option<CacheType> {
+        match self.eax & 0x1f {
+            1 => Some(CacheType::Data),
+            2 => Some(CacheType::Instruction),
+            3 => Some(CacheType::Unified),
+            _ => None,
+        }
+    }
+
+    /// The type of the cache.
+    pub fn cache_type(&self) -> CacheType {
+        self.try_cache_type().unwrap()
+    }
+
+    /// The level of the cache, starting at 1.
+    pub fn level(&self) -> u32 {
+        (self.eax >> 5) & 0x7
+    }
+
+    /// The size of a cache line in bytes.
+    pub fn line_size(&self) -> u32 {
+        (self.ebx & 0xfff) + 1
+    }
+
+    /// The number of physical line partitions.
+    pub fn partitions(&self) -> u32 {
+        ((self.ebx >> 12) & 0x3ff) + 1
+    }
+
+    /// The number of ways of associativity.
+    pub fn ways(&self) -> u32 {
+        ((self.ebx >> 22) & 0x3ff) + 1
+    }
+
+    /// The number of sets.
+    pub fn sets(&self) -> u32 {
+        self.ecx + 1
+    }
+
+    /// The size of the cache in bytes.
+    pub fn size(&self) -> u64 {
+        u64::from(self.ways())
+            * u64::from(self.partitions())
+            * u64::from(self.line_size())
+            * u64::from(self.sets())
+    }
+
+    /// The maximum number of logical processors sharing the cache.
+    pub fn shared_by(&self) -> u32 {
+        ((self.eax >> 14) & 0xfff) + 1
+    }
+
+    /// Is the cache fully associative?
+    pub fn is_fully_associative(&self) -> bool {
+        self.eax & (1 << 9) != 0
+    }
+
+    /// Does the cache include the lower level caches?
+    pub fn is_inclusive(&self) -> bool {
+        self.edx & (1 << 1) != 0
+    }
+}
+
+/// The caches of the host CPU.
+///
+/// ```ignore
+/// let caches = CacheInfo::new();
+/// if let Some(l2) = caches.data_cache(2) {
+///     let block = l2.size() / 2;
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CacheInfo {
+    descriptors: [CacheDescriptor; MAX_CACHES],
+    len: usize,
+}
+
+impl CacheInfo {
+    /// Enumerates the caches of the host CPU.
+    ///
+    /// If the CPU does not support the `cpuid` instruction, or does not
+    /// report its caches, there are no descriptors.
+    #[cfg_attr(feature = "cargo-clippy", allow(new_without_default))]
+    pub fn new() -> CacheInfo {
+        match Host::new() {
+            Some(host) => CacheInfo::from_query(host),
+            None => CacheInfo::empty(),
+        }
+    }
+
+    fn empty() -> CacheInfo {
+        CacheInfo {
+            descriptors: [CacheDescriptor {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            }; MAX_CACHES],
+            len: 0,
+        }
+    }
+
+    /// Enumerates the caches from the `cpuid` values of `x`.
+    fn from_query<T: CpuidQuery>(mut x: T) -> CacheInfo {
+        let mut info = CacheInfo::empty();
+        let leaf = match cache_leaf(&mut x) {
+            Some(leaf) => leaf,
+            None => return info,
+        };
+        for sub_leaf in 0..MAX_CACHES {
+            let CpuidResult { eax, ebx, ecx, edx } =
+                x.cpuid(leaf, sub_leaf as u32);
+            let descriptor = CacheDescriptor { eax, ebx, ecx, edx };
+            if descriptor.try_cache_type().is_none() {
+                break;
+            }
+            info.descriptors[sub_leaf] = descriptor;
+            info.len += 1;
+        }
+        info
+    }
+
+    /// The descriptors of the caches, in the order reported by the CPU.
+    pub fn descriptors(&self) -> &[CacheDescriptor] {
+        &self.descriptors[..self.len]
+    }
+
+    /// Returns an iterator over the descriptors of the caches.
+    pub fn iter(&self) -> slice::Iter<CacheDescriptor> {
+        self.descriptors().iter()
+    }
+
+    /// The data or unified cache of `level`, if any.
+    pub fn data_cache(&self, level: u32) -> Option<&CacheDescriptor> {
+        self.iter().find(|d| {
+            d.level() == level && d.cache_type() != CacheType::Instruction
+        })
+    }
+}
+
+/// Returns the leaf enumerating the caches, if the CPU has one.
+fn cache_leaf<T: CpuidQuery>(x: &mut T) -> Option<u32> {
+    let CpuidResult {
+        eax: max_basic_leaf,
+        ebx,
+        ecx,
+        edx,
+    } = x.cpuid(0, 0);
+    // CPUs supporting the topology extensions (EAX = 0x8000_0001: ECX[22]),
+    // i.e. AMD and Hygon, report their caches in leaf 0x8000_001D.
+    let max_extended_leaf = x.cpuid(0x8000_0000_u32, 0).eax;
+    if max_extended_leaf >= 0x8000_001d_u32
+        && x.cpuid(0x8000_0001_u32, 0).ecx & (1 << 22) != 0
+    {
+        return Some(0x8000_001d_u32);
+    }
+    // Leaf 4 is reserved on "AuthenticAMD" and "HygonGenuine" (in EBX, EDX
+    // and ECX):
+    let vendor = [ebx, edx, ecx];
+    if vendor == [0x6874_7541, 0x6974_6e65, 0x444d_4163]
+        || vendor == [0x6f67_7948, 0x6e65_476e, 0x656e_6975]
+    {
+        None
+    } else if max_basic_leaf >= 4 {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+/// The type of a level of the topology.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TopologyLevelType {
+    /// Logical processors (threads) of a core
+    Smt,
+    /// Cores
+    Core,
+    /// Modules
+    Module,
+    /// Tiles
+    Tile,
+    /// Dies
+    Die,
+    /// A level type unknown to this crate.
+    Other(u32),
+}
+
+/// A level of the topology, as reported by one sub-leaf of leaf 0xB or
+/// 0x1F.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TopologyLevel {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+}
+
+impl TopologyLevel {
+    /// The type of the level.
+    pub fn level_type(&self) -> TopologyLevelType {
+        match (self.ecx >> 8) & 0xff {
+            1 => TopologyLevelType::Smt,
+            2 => TopologyLevelType::Core,
+            3 => TopologyLevelType::Module,
+            4 => TopologyLevelType::Tile,
+            5 => TopologyLevelType::Die,
+            t => TopologyLevelType::Other(t),
+        }
+    }
+
+    /// The number of logical processors within this level, as configured
+    /// by the firmware.
+    pub fn logical_processors(&self) -> u32 {
+        self.ebx & 0xffff
+    }
+
+    /// The number of bits to shift the x2APIC ID right to get the ID of
+    /// the next level.
+    pub fn shift(&self) -> u32 {
+        self.eax & 0x1f
+    }
+}
+
+/// The core/thread topology of the package of the host CPU.
+///
+/// ```ignore
+/// if let Some(topology) = Topology::new() {
+///     let cores = topology.logical_processors()
+///         / topology.threads_per_core();
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Topology {
+    levels: [TopologyLevel; MAX_LEVELS],
+    len: usize,
+    x2apic_id: u32,
+}
+
+impl Topology {
+    /// Enumerates the topology of the host CPU.
+    ///
+    /// Returns `None` if the CPU does not support the `cpuid` instruction or
+    /// the extended topology leaves.
+    pub fn new() -> Option<Topology> {
+        Host::new().and_then(Topology::from_query)
+    }
+
+    /// Enumerates the topology from the `cpuid` values of `x`.
+    fn from_query<T: CpuidQuery>(mut x: T) -> Option<Topology> {
+        let max_basic_leaf = x.cpuid(0, 0).eax;
+        // Prefer leaf 0x1F, which also reports modules, tiles and dies, if
+        // it is supported and valid: its sub-leaf 0 must report a level.
+        let leaf = if max_basic_leaf >= 0x1f && x.cpuid(0x1f, 0).ebx != 0 {
+            0x1f
+        } else if max_basic_leaf >= 0xb && x.cpuid(0xb, 0).ebx != 0 {
+            0xb
+        } else {
+            return None;
+        };
+
+        let mut topology = Topology {
+            levels: [TopologyLevel {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+            }; MAX_LEVELS],
+            len: 0,
+            x2apic_id: x.cpuid(leaf, 0).edx,
+        };
+        for sub_leaf in 0..MAX_LEVELS {
+            let CpuidResult { eax, ebx, ecx, .. } =
+                x.cpuid(leaf, sub_leaf as u32);
+            // The level type is 0 past the last level.
+            if (ecx >> 8) & 0xff == 0 {
+                break;
+            }
+            topology.levels[sub_leaf] = TopologyLevel { eax, ebx, ecx };
+            topology.len += 1;
+        }
+        Some(topology)
+    }
+
+    /// The levels of the topology, from the innermost (SMT) outwards.
+    pub fn levels(&self) -> &[TopologyLevel] {
+        &self.levels[..self.len]
+    }
+
+    /// The x2APIC ID of the logical processor that ran the query.
+    pub fn x2apic_id(&self) -> u32 {
+        self.x2apic_id
+    }
+
+    /// The number of logical processors of a core.
+    pub fn threads_per_core(&self) -> u32 {
+        self.levels()
+            .iter()
+            .find(|l| l.level_type() == TopologyLevelType::Smt)
+            .map_or(1, |l| l.logical_processors())
+    }
+
+    /// The number of logical processors of the package.
+    pub fn logical_processors(&self) -> u32 {
+        self.levels()
+            .last()
+            .map_or(1, |l| l.logical_processors())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use runtime::dumps::{self, Dump};
+
+    use super::*;
+
+    /// `(level, type, size, ways, line size, sets, shared by)`
+    type Expected = (u32, CacheType, u64, u32, u32, u32, u32);
+
+    fn assert_caches(info: &CacheInfo, expected: &[Expected]) {
+        assert_eq!(info.descriptors().len(), expected.len());
+        for (d, e) in info.iter().zip(expected) {
+            let found = (
+                d.level(),
+                d.cache_type(),
+                d.size(),
+                d.ways(),
+                d.line_size(),
+                d.sets(),
+                d.shared_by(),
+            );
+            assert_eq!(found, *e);
+            assert_eq!(d.partitions(), 1);
+            assert!(!d.is_fully_associative());
+        }
+    }
+
+    #[test]
+    fn intel_caches() {
+        let info = CacheInfo::from_query(dumps::SKYLAKE);
+        assert_caches(
+            &info,
+            &[
+                (1, CacheType::Data, 32 << 10, 8, 64, 64, 2),
+                (1, CacheType::Instruction, 32 << 10, 8, 64, 64, 2),
+                (2, CacheType::Unified, 256 << 10, 4, 64, 1024, 2),
+                (3, CacheType::Unified, 8 << 20, 16, 64, 8192, 16),
+            ],
+        );
+        assert!(info.descriptors()[3].is_inclusive());
+        assert_eq!(info.data_cache(1), Some(&info.descriptors()[0]));
+        assert_eq!(info.data_cache(3).map(|d| d.size()), Some(8 << 20));
+        assert_eq!(info.data_cache(4), None);
+    }
+
+    #[test]
+    fn amd_caches() {
+        let info = CacheInfo::from_query(dumps::ZEN);
+        assert_caches(
+            &info,
+            &[
+                (1, CacheType::Data, 32 << 10, 8, 64, 64, 2),
+                (1, CacheType::Instruction, 64 << 10, 4, 64, 256, 2),
+                (2, CacheType::Unified, 512 << 10, 8, 64, 1024, 2),
+                (3, CacheType::Unified, 8 << 20, 16, 64, 8192, 8),
+            ],
+        );
+        assert!(info.descriptors()[2].is_inclusive());
+        assert!(!info.descriptors()[3].is_inclusive());
+    }
+
+    #[test]
+    fn amd_without_topology_extensions() {
+        let mut leaves = dumps::ZEN.leaves.to_vec();
+        for l in &mut leaves {
+            if l.0 == 0x8000_0001 {
+                l.2[2] &= !(1 << 22);
+            }
+        }
+        let info = CacheInfo::from_query(Dump {
+            leaves: &leaves,
+            xcr0: 0x7,
+        });
+        assert!(info.descriptors().is_empty());
+        assert_eq!(info.data_cache(1), None);
+    }
+
+    #[test]
+    fn hygon_caches() {
+        // Hygon Dhyana is a Zen core with its own vendor ID.
+        let mut leaves = dumps::ZEN.leaves.to_vec();
+        for l in &mut leaves {
+            if l.0 == 0 {
+                l.2[1] = 0x6f67_7948;
+                l.2[2] = 0x656e_6975;
+                l.2[3] = 0x6e65_476e;
+            }
+        }
+        let hygon = CacheInfo::from_query(Dump {
+            leaves: &leaves,
+            xcr0: 0x7,
+        });
+        let zen = CacheInfo::from_query(dumps::ZEN);
+        assert_eq!(hygon.descriptors().len(), 4);
+        assert_eq!(hygon.descriptors(), zen.descriptors());
+    }
+
+    #[test]
+    fn intel_topology() {
+        let topology = Topology::from_query(dumps::SKYLAKE).unwrap();
+        let levels = topology.levels();
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].level_type(), TopologyLevelType::Smt);
+        assert_eq!(levels[0].shift(), 1);
+        assert_eq!(levels[1].level_type(), TopologyLevelType::Core);
+        assert_eq!(levels[1].shift(), 4);
+        assert_eq!(topology.x2apic_id(), 3);
+        assert_eq!(topology.threads_per_core(), 2);
+        assert_eq!(topology.logical_processors(), 8);
+    }
+
+    #[test]
+    fn v2_topology() {
+        // A CPU reporting dies in leaf 0x1F: 2 dies of 4 cores of 1 thread.
+        let topology = Topology::from_query(Dump {
+            leaves: &[
+                (0, 0, [0x1f, 0, 0, 0]),
+                (0xb, 0, [0x0, 0x1, 0x100, 0x5]),
+                (0xb, 1, [0x3, 0x8, 0x201, 0x5]),
+                (0x1f, 0, [0x0, 0x1, 0x100, 0x5]),
+                (0x1f, 1, [0x2, 0x4, 0x201, 0x5]),
+                (0x1f, 2, [0x3, 0x8, 0x502, 0x5]),
+            ],
+            xcr0: 0,
+        }).unwrap();
+        let types: Vec<_> =
+            topology.levels().iter().map(|l| l.level_type()).collect();
+        assert_eq!(
+            types,
+            [
+                TopologyLevelType::Smt,
+                TopologyLevelType::Core,
+                TopologyLevelType::Die
+            ]
+        );
+        assert_eq!(topology.threads_per_core(), 1);
+        assert_eq!(topology.logical_processors(), 8);
+        assert_eq!(topology.x2apic_id(), 5);
+    }
+
+    #[test]
+    fn no_topology() {
+        // Zen only reports its topology in AMD specific leaves.
+        assert!(Topology::from_query(dumps::ZEN).is_none());
+    }
+
+    #[test]
+    fn host() {
+        let caches = CacheInfo::new();
+        for d in caches.iter() {
+            println!(
+                "L{} {:?}: {} bytes, {} ways, {} byte lines",
+                d.level(),
+                d.cache_type(),
+                d.size(),
+                d.ways(),
+                d.line_size()
+            );
+        }
+        if let Some(topology) = Topology::new() {
+            println!("{:?}", topology.levels());
+            assert!(topology.threads_per_core() >= 1);
+        }
+    }
+}
@@ -2,23 +2,28 @@
 //!
 //! The features are detected using the `detect_features` function below.
 //! This function uses the CPUID instruction to read the feature flags from the
-//! CPU and encodes them in an `usize` where each bit position represents
-//! whether a feature is available (bit is set) or unavaiable (bit is cleared).
+//! CPU and encodes them in a `cache::Initializer` bitset where each bit
+//! position represents whether a feature is available (bit is set) or
+//! unavaiable (bit is cleared).
 //!
 //! The enum `__Feature` is used to map bit positions to feature names, and the
-//! the `__unstable_detect_feature!` macro is used to map string literals (e.g.
+//! the `__unstable_feature!` macro is used to map string literals (e.g.
 //! "avx") to these bit positions (e.g. `__Feature::avx`).
 //!
 //!
 //! The run-time feature detection is performed by the
 //! `__unstable_detect_feature(__Feature) -> bool` function. On its first call,
 //! this functions queries the CPU for the available features and stores them
-//! in a global `AtomicUsize` variable. The query is performed by just checking
+//! in a global multi-word bitset. The query is performed by just checking
 //! whether the feature bit in this global variable is set or cleared.
 
 use core::mem;
 
 use super::bit;
+use super::cache;
+use vendor::CpuidResult;
+
+use super::{CpuidQuery, Host};
 
 /// This macro maps the string-literal feature names to values of the
 /// `__Feature` enum at compile-time. The feature names used are the same as
@@ -28,138 +33,201 @@ use super::bit;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 #[macro_export]
 #[doc(hidden)]
-macro_rules! __unstable_detect_feature {
+macro_rules! __unstable_feature {
     ("mmx") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::mmx{})  };
+        $crate::vendor::__Feature::mmx{}
+    };
     ("sse") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::sse{})  };
+        $crate::vendor::__Feature::sse{}
+    };
     ("sse2") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::sse2{})
+        $crate::vendor::__Feature::sse2{}
     };
     ("sse3") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::sse3{})
+        $crate::vendor::__Feature::sse3{}
     };
     ("ssse3") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::ssse3{})
+        $crate::vendor::__Feature::ssse3{}
     };
     ("sse4.1") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::sse4_1{})
+        $crate::vendor::__Feature::sse4_1{}
     };
     ("sse4.2") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::sse4_2{})
+        $crate::vendor::__Feature::sse4_2{}
     };
     ("sse4a") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::sse4a{})
+        $crate::vendor::__Feature::sse4a{}
     };
     ("avx") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx{})
+        $crate::vendor::__Feature::avx{}
     };
     ("avx2") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx2{})
+        $crate::vendor::__Feature::avx2{}
     };
     ("avx512f") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512f{})
+        $crate::vendor::__Feature::avx512f{}
     };
     ("avx512cd") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512cd{})
+        $crate::vendor::__Feature::avx512cd{}
     };
     ("avx512er") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512er{})
+        $crate::vendor::__Feature::avx512er{}
     };
     ("avx512pf") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512pf{})
+        $crate::vendor::__Feature::avx512pf{}
     };
     ("avx512bw") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512bw{})
+        $crate::vendor::__Feature::avx512bw{}
     };
     ("avx512dq") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512dq{})
+        $crate::vendor::__Feature::avx512dq{}
     };
     ("avx512vl") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512vl{})
+        $crate::vendor::__Feature::avx512vl{}
     };
     ("avx512ifma") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512_ifma{})
+        $crate::vendor::__Feature::avx512_ifma{}
     };
     ("avx512vbmi") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512_vbmi{})
+        $crate::vendor::__Feature::avx512_vbmi{}
     };
     ("avx512vpopcntdq") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::avx512_vpopcntdq{})
+        $crate::vendor::__Feature::avx512_vpopcntdq{}
+    };
+    ("avx512vnni") => {
+        $crate::vendor::__Feature::avx512_vnni{}
+    };
+    ("avx512bitalg") => {
+        $crate::vendor::__Feature::avx512_bitalg{}
+    };
+    ("avx512vbmi2") => {
+        $crate::vendor::__Feature::avx512_vbmi2{}
+    };
+    ("avx512bf16") => {
+        $crate::vendor::__Feature::avx512_bf16{}
+    };
+    ("avx512vp2intersect") => {
+        $crate::vendor::__Feature::avx512_vp2intersect{}
+    };
+    ("gfni") => {
+        $crate::vendor::__Feature::gfni{}
+    };
+    ("vaes") => {
+        $crate::vendor::__Feature::vaes{}
+    };
+    ("vpclmulqdq") => {
+        $crate::vendor::__Feature::vpclmulqdq{}
     };
     ("fma") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::fma{})
+        $crate::vendor::__Feature::fma{}
+    };
+    ("f16c") => {
+        $crate::vendor::__Feature::f16c{}
     };
     ("bmi") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::bmi{})
+        $crate::vendor::__Feature::bmi{}
     };
     ("bmi2") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::bmi2{})
+        $crate::vendor::__Feature::bmi2{}
     };
     ("abm") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::abm{})
+        $crate::vendor::__Feature::abm{}
     };
     ("lzcnt") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::abm{})
+        $crate::vendor::__Feature::lzcnt{}
     };
     ("tbm") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::tbm{})
+        $crate::vendor::__Feature::tbm{}
     };
     ("popcnt") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::popcnt{})
+        $crate::vendor::__Feature::popcnt{}
     };
     ("fxsr") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::fxsr{})
+        $crate::vendor::__Feature::fxsr{}
     };
     ("xsave") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::xsave{})
+        $crate::vendor::__Feature::xsave{}
     };
     ("xsaveopt") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::xsaveopt{})
+        $crate::vendor::__Feature::xsaveopt{}
     };
     ("xsaves") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::xsaves{})
+        $crate::vendor::__Feature::xsaves{}
     };
     ("xsavec") => {
-        $crate::vendor::__unstable_detect_feature(
-            $crate::vendor::__Feature::xsavec{})
+        $crate::vendor::__Feature::xsavec{}
+    };
+    ("aes") => {
+        $crate::vendor::__Feature::aes{}
+    };
+    ("pclmulqdq") => {
+        $crate::vendor::__Feature::pclmulqdq{}
+    };
+    ("sha") => {
+        $crate::vendor::__Feature::sha{}
+    };
+    ("rdrand") => {
+        $crate::vendor::__Feature::rdrand{}
+    };
+    ("rdseed") => {
+        $crate::vendor::__Feature::rdseed{}
+    };
+    ("adx") => {
+        $crate::vendor::__Feature::adx{}
+    };
+    ("movbe") => {
+        $crate::vendor::__Feature::movbe{}
+    };
+    ("cmpxchg16b") => {
+        $crate::vendor::__Feature::cmpxchg16b{}
+    };
+    ("lahfsahf") => {
+        $crate::vendor::__Feature::lahfsahf{}
+    };
+    ("x86-64-v1") => { __unstable_feature!(@level "x86-64-v1") };
+    ("x86-64-v2") => { __unstable_feature!(@level "x86-64-v2") };
+    ("x86-64-v3") => { __unstable_feature!(@level "x86-64-v3") };
+    ("x86-64-v4") => { __unstable_feature!(@level "x86-64-v4") };
+    (@level $t:tt) => {
+        compile_error!(concat!(
+            "microarchitecture level ", $t, " cannot be used in all(...) ",
+            "or any(...), use cfg_feature_enabled!(\"", $t, "\") instead"
+        ))
     };
     ($t:tt) => {
         compile_error!(concat!("unknown target feature: ", $t))
     };
 }
 
+/// This macro performs the run-time detection of a feature, or of an x86-64
+/// microarchitecture level.
+///
+/// PLESE: do not use this, it is an implementation detail subjected to change.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __unstable_detect_feature {
+    ("x86-64-v1") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V1)
+    };
+    ("x86-64-v2") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V2)
+    };
+    ("x86-64-v3") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V3)
+    };
+    ("x86-64-v4") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V4)
+    };
+    ($t:tt) => {
+        $crate::vendor::__unstable_detect_feature(__unstable_feature!($t))
+    };
+}
+
 /// X86 CPU Feature enum. Each variant denotes a position in a bitset for a
 /// particular feature.
 ///
@@ -211,6 +279,8 @@ pub enum __Feature {
     avx512_vpopcntdq,
     /// FMA (Fused Multiply Add)
     fma,
+    /// F16C (Half-precision floating-point conversions)
+    f16c,
     /// BMI1 (Bit Manipulation Instructions 1)
     bmi,
     /// BMI1 (Bit Manipulation Instructions 2)
@@ -232,9 +302,102 @@ pub enum __Feature {
     xsaves,
     /// XSAVEC (Save Processor Extended States Compacted)
     xsavec,
+    /// AES (Advanced Encryption Standard New Instructions)
+    aes,
+    /// PCLMULQDQ (Carry-less Multiplication Quadword)
+    pclmulqdq,
+    /// SHA (Secure Hash Algorithm Extensions)
+    sha,
+    /// RDRAND (Read Random Number)
+    rdrand,
+    /// RDSEED (Read Random Seed)
+    rdseed,
+    /// ADX (Multi-Precision Add-Carry Instruction Extensions)
+    adx,
+    /// MOVBE (Move Data After Swapping Bytes)
+    movbe,
+    /// LZCNT (Leading Zero Count)
+    lzcnt,
+    /// CMPXCHG16B (Compare and Exchange Bytes, 128-bit)
+    cmpxchg16b,
+    /// AVX-512 VNNI (Vector Neural Network Instructions)
+    avx512_vnni,
+    /// AVX-512 BITALG (Bit Algorithms)
+    avx512_bitalg,
+    /// AVX-512 VBMI2 (Vector Byte Manipulation Instructions 2)
+    avx512_vbmi2,
+    /// AVX-512 BF16 (BFloat16 Instructions)
+    avx512_bf16,
+    /// AVX-512 VP2INTERSECT (Vector Pair Intersection)
+    avx512_vp2intersect,
+    /// GFNI (Galois Field New Instructions)
+    gfni,
+    /// VAES (Vector AES Instructions)
+    vaes,
+    /// VPCLMULQDQ (Vector Carry-less Multiplication Quadword)
+    vpclmulqdq,
+    /// LAHF/SAHF (Load/Store AH from/into Flags in 64-bit mode)
+    lahfsahf,
     #[doc(hidden)] __NonExhaustive,
 }
 
+/// The `cfg_feature_enabled!` names of the features, in `__Feature` order.
+pub const FEATURE_NAMES: &[&str] = &[
+    "mmx",
+    "sse",
+    "sse2",
+    "sse3",
+    "ssse3",
+    "sse4.1",
+    "sse4.2",
+    "sse4a",
+    "avx",
+    "avx2",
+    "avx512f",
+    "avx512cd",
+    "avx512er",
+    "avx512pf",
+    "avx512bw",
+    "avx512dq",
+    "avx512vl",
+    "avx512ifma",
+    "avx512vbmi",
+    "avx512vpopcntdq",
+    "fma",
+    "f16c",
+    "bmi",
+    "bmi2",
+    "abm",
+    "tbm",
+    "popcnt",
+    "fxsr",
+    "xsave",
+    "xsaveopt",
+    "xsaves",
+    "xsavec",
+    "aes",
+    "pclmulqdq",
+    "sha",
+    "rdrand",
+    "rdseed",
+    "adx",
+    "movbe",
+    "lzcnt",
+    "cmpxchg16b",
+    "avx512vnni",
+    "avx512bitalg",
+    "avx512vbmi2",
+    "avx512bf16",
+    "avx512vp2intersect",
+    "gfni",
+    "vaes",
+    "vpclmulqdq",
+    "lahfsahf",
+];
+
+/// Names accepted by `cfg_feature_enabled!` for another feature.
+pub const FEATURE_ALIASES: &[(&str, &str)] = &[];
+
 /// Run-time feature detection on x86 works by using the CPUID instruction.
 ///
 /// The [CPUID Wikipedia page][wiki_cpuid] contains
@@ -250,38 +413,35 @@ pub enum __Feature {
 /// [wiki_cpuid]: https://en.wikipedia.org/wiki/CPUID
 /// [intel64_ref]: http://www.intel.de/content/dam/www/public/us/en/documents/manuals/64-ia-32-architectures-software-developer-instruction-set-reference-manual-325383.pdf
 /// [amd64_ref]: http://support.amd.com/TechDocs/24594.pdf
-pub fn detect_features() -> usize {
-    use vendor::{__cpuid, __cpuid_count, has_cpuid, CpuidResult};
-    use vendor::_xgetbv;
-    let mut value: usize = 0;
-
+pub fn detect_features() -> cache::Initializer {
     // If the x86 CPU does not support the CPUID instruction then it is too
     // old to support any of the currently-detectable features.
-    if !has_cpuid() {
-        return value;
+    match Host::new() {
+        Some(host) => detect_features_impl(host),
+        None => cache::Initializer::default(),
     }
+}
 
-    // Calling `__cpuid`/`__cpuid_count` from here on is safe because the CPU
-    // has `cpuid` support.
+/// Detects the features from the `cpuid` and XCR0 values of `x`.
+pub fn detect_features_impl<T: CpuidQuery>(mut x: T) -> cache::Initializer {
+    let mut value = cache::Initializer::default();
 
     // 0. EAX = 0: Basic Information:
     // - EAX returns the "Highest Function Parameter", that is, the maximum
     // leaf value for subsequent calls of `cpuinfo` in range [0,
     // 0x8000_0000]. - The vendor ID is stored in 12 u8 ascii chars,
     // returned in EBX, EDX, and   ECX (in that order):
-    let (max_basic_leaf, vendor_id) = unsafe {
+    let (max_basic_leaf, vendor_id) = {
         let CpuidResult {
             eax: max_basic_leaf,
             ebx,
             ecx,
             edx,
-        } = __cpuid(0);
-        let vendor_id: [[u8; 4]; 3] = [
-            mem::transmute(ebx),
-            mem::transmute(edx),
-            mem::transmute(ecx),
-        ];
-        let vendor_id: [u8; 12] = mem::transmute(vendor_id);
+        } = x.cpuid(0, 0);
+        let vendor_id: [[u8; 4]; 3] = unsafe {
+            [mem::transmute(ebx), mem::transmute(edx), mem::transmute(ecx)]
+        };
+        let vendor_id: [u8; 12] = unsafe { mem::transmute(vendor_id) };
         (max_basic_leaf, vendor_id)
     };
 
@@ -296,16 +456,28 @@ pub fn detect_features() -> usize {
         ecx: proc_info_ecx,
         edx: proc_info_edx,
         ..
-    } = unsafe { __cpuid(0x0000_0001_u32) };
+    } = x.cpuid(0x0000_0001_u32, 0);
 
     // EAX = 7, ECX = 0: Queries "Extended Features";
     // Contains information about bmi,bmi2, and avx2 support.
-    let (extended_features_ebx, extended_features_ecx) = if max_basic_leaf >= 7
-    {
-        let CpuidResult { ebx, ecx, .. } = unsafe { __cpuid(0x0000_0007_u32) };
-        (ebx, ecx)
+    // - EAX returns the highest supported sub-leaf of leaf 7.
+    let (
+        extended_features_ebx,
+        extended_features_ecx,
+        extended_features_edx,
+        extended_features1_eax,
+    ) = if max_basic_leaf >= 7 {
+        let CpuidResult { eax, ebx, ecx, edx } = x.cpuid(0x0000_0007_u32, 0);
+        // EAX = 7, ECX = 1: Queries "Extended Features" sub-leaf 1;
+        // Contains information about avx512_bf16 support.
+        let eax1 = if eax >= 1 {
+            x.cpuid(0x0000_0007_u32, 1).eax
+        } else {
+            0
+        };
+        (ebx, ecx, edx, eax1)
     } else {
-        (0, 0) // CPUID does not support "Extended Features"
+        (0, 0, 0, 0) // CPUID does not support "Extended Features"
     };
 
     // EAX = 0x8000_0000, ECX = 0: Get Highest Extended Function Supported
@@ -314,12 +486,12 @@ pub fn detect_features() -> usize {
     let CpuidResult {
         eax: extended_max_basic_leaf,
         ..
-    } = unsafe { __cpuid(0x8000_0000_u32) };
+    } = x.cpuid(0x8000_0000_u32, 0);
 
     // EAX = 0x8000_0001, ECX=0: Queries "Extended Processor Info and Feature
     // Bits"
     let extended_proc_info_ecx = if extended_max_basic_leaf >= 1 {
-        let CpuidResult { ecx, .. } = unsafe { __cpuid(0x8000_0001_u32) };
+        let CpuidResult { ecx, .. } = x.cpuid(0x8000_0001_u32, 0);
         ecx
     } else {
         0
@@ -329,16 +501,20 @@ pub fn detect_features() -> usize {
         // borrows value till the end of this scope:
         let mut enable = |r, rb, f| {
             if bit::test(r as usize, rb) {
-                value = bit::set(value, f as u32);
+                value.set(f as u32);
             }
         };
 
         enable(proc_info_ecx, 0, __Feature::sse3);
+        enable(proc_info_ecx, 1, __Feature::pclmulqdq);
         enable(proc_info_ecx, 9, __Feature::ssse3);
-        enable(proc_info_ecx, 12, __Feature::fma);
+        enable(proc_info_ecx, 13, __Feature::cmpxchg16b);
         enable(proc_info_ecx, 19, __Feature::sse4_1);
         enable(proc_info_ecx, 20, __Feature::sse4_2);
+        enable(proc_info_ecx, 22, __Feature::movbe);
         enable(proc_info_ecx, 23, __Feature::popcnt);
+        enable(proc_info_ecx, 25, __Feature::aes);
+        enable(proc_info_ecx, 30, __Feature::rdrand);
         enable(proc_info_edx, 24, __Feature::fxsr);
         enable(proc_info_edx, 23, __Feature::mmx);
         enable(proc_info_edx, 25, __Feature::sse);
@@ -346,6 +522,17 @@ pub fn detect_features() -> usize {
 
         enable(extended_features_ebx, 3, __Feature::bmi);
         enable(extended_features_ebx, 8, __Feature::bmi2);
+        enable(extended_features_ebx, 18, __Feature::rdseed);
+        enable(extended_features_ebx, 19, __Feature::adx);
+        enable(extended_features_ebx, 29, __Feature::sha);
+
+        // `aes`, `pclmulqdq` and `sha` only cover the legacy SSE encodings,
+        // which use the XMM registers saved by `fxsave`: unlike the VEX
+        // encoded features below they do not depend on XCR0.
+        //
+        // The same holds for `gfni`, whose VEX and EVEX encodings are
+        // covered by `avx` and `avx512f`.
+        enable(extended_features_ecx, 8, __Feature::gfni);
 
         // `XSAVE` and `AVX` support:
         let cpu_xsave = bit::test(proc_info_ecx as usize, 26);
@@ -368,9 +555,19 @@ pub fn detect_features() -> usize {
             // `XCR0.AVX[2]` to `1`.
             //
             // This is safe because the CPU supports `xsave`
-            let xcr0 = unsafe { _xgetbv(0) };
+            let xcr0 = x.xcr0();
             let os_avx_support = xcr0 & 6 == 6;
-            let os_avx512_support = xcr0 & 224 == 224;
+
+            // 3. For AVX-512 the OS must also save and restore the opmask
+            // registers `k0-k7` (`XCR0.opmask[5]`), the upper halves of
+            // `zmm0-zmm15` (`XCR0.ZMM_Hi256[6]`), and `zmm16-zmm31`
+            // (`XCR0.Hi16_ZMM[7]`):
+            let os_opmask_support = bit::test(xcr0 as usize, 5);
+            let os_zmm_hi256_support = bit::test(xcr0 as usize, 6);
+            let os_hi16_zmm_support = bit::test(xcr0 as usize, 7);
+            let os_avx512_support = os_opmask_support
+                && os_zmm_hi256_support
+                && os_hi16_zmm_support;
 
             // Only if the OS and the CPU support saving/restoring the AVX
             // registers we enable `xsave` support:
@@ -394,7 +591,7 @@ pub fn detect_features() -> usize {
                     let CpuidResult {
                         eax: proc_extended_state1_eax,
                         ..
-                    } = unsafe { __cpuid_count(0xd_u32, 1) };
+                    } = x.cpuid(0xd_u32, 1);
                     enable(proc_extended_state1_eax, 0, __Feature::xsaveopt);
                     enable(proc_extended_state1_eax, 1, __Feature::xsavec);
                     enable(proc_extended_state1_eax, 3, __Feature::xsaves);
@@ -402,7 +599,15 @@ pub fn detect_features() -> usize {
 
                 // And AVX/AVX2:
                 enable(proc_info_ecx, 28, __Feature::avx);
+                // FMA and F16C are VEX-encoded, so they also require OS AVX
+                // support:
+                enable(proc_info_ecx, 12, __Feature::fma);
+                enable(proc_info_ecx, 29, __Feature::f16c);
                 enable(extended_features_ebx, 5, __Feature::avx2);
+                // VAES and VPCLMULQDQ are also available without AVX-512,
+                // on 256-bit vectors:
+                enable(extended_features_ecx, 9, __Feature::vaes);
+                enable(extended_features_ecx, 10, __Feature::vpclmulqdq);
 
                 // For AVX-512 the OS also needs to support saving/restoring
                 // the extended state, only then we enable AVX-512 support:
@@ -421,18 +626,30 @@ pub fn detect_features() -> usize {
                         14,
                         __Feature::avx512_vpopcntdq,
                     );
+                    enable(extended_features_ecx, 6, __Feature::avx512_vbmi2);
+                    enable(extended_features_ecx, 11, __Feature::avx512_vnni);
+                    enable(
+                        extended_features_ecx,
+                        12,
+                        __Feature::avx512_bitalg,
+                    );
+                    enable(
+                        extended_features_edx,
+                        8,
+                        __Feature::avx512_vp2intersect,
+                    );
+                    enable(extended_features1_eax, 5, __Feature::avx512_bf16);
                 }
             }
         }
 
         // This detects ABM on AMD CPUs and LZCNT on Intel CPUs.
         // On intel CPUs with popcnt, lzcnt implements the
-        // "missing part" of ABM, so we map both to the same
-        // internal feature.
-        //
-        // The `cfg_feature_enabled!("lzcnt")` macro then
-        // internally maps to __Feature::abm.
+        // "missing part" of ABM. Both are reported by the same bit, but
+        // `lzcnt` is the name used by `target_feature`.
         enable(extended_proc_info_ecx, 5, __Feature::abm);
+        enable(extended_proc_info_ecx, 5, __Feature::lzcnt);
+        enable(extended_proc_info_ecx, 0, __Feature::lahfsahf);
         if vendor_id == *b"AuthenticAMD" {
             // These features are only available on AMD CPUs:
             enable(extended_proc_info_ecx, 6, __Feature::sse4a);
@@ -447,6 +664,23 @@ pub fn detect_features() -> usize {
 mod tests {
     extern crate cupid;
 
+    use super::{cache, detect_features_impl, FEATURE_NAMES, __Feature};
+    use runtime::dumps::{self, Dump};
+    use runtime::{is_detected, Feature};
+
+    #[test]
+    fn feature_names() {
+        assert_eq!(FEATURE_NAMES.len(), __Feature::__NonExhaustive as usize);
+        let f = |name: &str| name.parse::<Feature>().unwrap();
+        assert_eq!(is_detected(f("sse2")), cfg_feature_enabled!("sse2"));
+        assert_eq!(is_detected(f("avx2")), cfg_feature_enabled!("avx2"));
+        assert_eq!(is_detected(f("lzcnt")), cfg_feature_enabled!("lzcnt"));
+        assert_eq!(
+            is_detected(f("sse4.1")),
+            __unstable_detect_feature!("sse4.1")
+        );
+    }
+
     #[test]
     fn dump() {
         println!("sse: {:?}", cfg_feature_enabled!("sse"));
@@ -472,6 +706,7 @@ mod tests {
             cfg_feature_enabled!("avx512vpopcntdq")
         );
         println!("fma: {:?}", cfg_feature_enabled!("fma"));
+        println!("f16c: {:?}", cfg_feature_enabled!("f16c"));
         println!("abm: {:?}", cfg_feature_enabled!("abm"));
         println!("bmi: {:?}", cfg_feature_enabled!("bmi"));
         println!("bmi2: {:?}", cfg_feature_enabled!("bmi2"));
@@ -483,6 +718,194 @@ mod tests {
         println!("xsaveopt: {:?}", cfg_feature_enabled!("xsaveopt"));
         println!("xsaves: {:?}", cfg_feature_enabled!("xsaves"));
         println!("xsavec: {:?}", cfg_feature_enabled!("xsavec"));
+        println!("aes: {:?}", cfg_feature_enabled!("aes"));
+        println!("pclmulqdq: {:?}", cfg_feature_enabled!("pclmulqdq"));
+        println!("sha: {:?}", cfg_feature_enabled!("sha"));
+        println!("rdrand: {:?}", cfg_feature_enabled!("rdrand"));
+        println!("rdseed: {:?}", cfg_feature_enabled!("rdseed"));
+        println!("adx: {:?}", cfg_feature_enabled!("adx"));
+        println!("movbe: {:?}", cfg_feature_enabled!("movbe"));
+        println!("cmpxchg16b: {:?}", cfg_feature_enabled!("cmpxchg16b"));
+        println!("lahfsahf: {:?}", cfg_feature_enabled!("lahfsahf"));
+        println!("avx512vnni: {:?}", cfg_feature_enabled!("avx512vnni"));
+        println!("avx512bitalg: {:?}", cfg_feature_enabled!("avx512bitalg"));
+        println!("avx512vbmi2: {:?}", cfg_feature_enabled!("avx512vbmi2"));
+        println!("avx512bf16: {:?}", cfg_feature_enabled!("avx512bf16"));
+        println!(
+            "avx512vp2intersect: {:?}",
+            cfg_feature_enabled!("avx512vp2intersect")
+        );
+        println!("gfni: {:?}", cfg_feature_enabled!("gfni"));
+        println!("vaes: {:?}", cfg_feature_enabled!("vaes"));
+        println!("vpclmulqdq: {:?}", cfg_feature_enabled!("vpclmulqdq"));
+    }
+
+    fn assert_features(dump: Dump, expected: &[&str]) {
+        let value = detect_features_impl(dump);
+        for (i, name) in FEATURE_NAMES.iter().enumerate() {
+            assert_eq!(
+                value.test(i as u32),
+                expected.contains(name),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn recorded_skylake() {
+        assert_features(
+            dumps::SKYLAKE,
+            &[
+                "mmx", "sse", "sse2", "sse3", "ssse3", "sse4.1", "sse4.2",
+                "avx", "avx2", "fma", "f16c", "bmi", "bmi2", "abm",
+                "popcnt", "fxsr", "xsave", "xsaveopt", "xsaves", "xsavec",
+                "aes", "pclmulqdq", "rdrand", "rdseed", "adx", "movbe",
+                "lzcnt", "cmpxchg16b",
+                "lahfsahf",
+            ],
+        );
+    }
+
+    #[test]
+    fn recorded_skylake_x() {
+        assert_features(
+            dumps::SKYLAKE_X,
+            &[
+                "mmx", "sse", "sse2", "sse3", "ssse3", "sse4.1", "sse4.2",
+                "avx", "avx2", "avx512f", "avx512cd", "avx512bw",
+                "avx512dq", "avx512vl", "fma", "f16c", "bmi", "bmi2", "abm",
+                "popcnt", "fxsr", "xsave", "xsaveopt", "xsaves", "xsavec",
+                "aes", "pclmulqdq", "rdrand", "rdseed", "adx", "movbe",
+                "lzcnt", "cmpxchg16b",
+                "lahfsahf",
+            ],
+        );
+    }
+
+    #[test]
+    fn recorded_zen() {
+        assert_features(
+            dumps::ZEN,
+            &[
+                "mmx", "sse", "sse2", "sse3", "ssse3", "sse4.1", "sse4.2",
+                "sse4a", "avx", "avx2", "fma", "f16c", "bmi", "bmi2", "abm",
+                "popcnt", "fxsr", "xsave", "xsaveopt", "xsaves", "xsavec",
+                "aes", "pclmulqdq", "sha", "rdrand", "rdseed", "adx",
+                "movbe", "lzcnt", "cmpxchg16b",
+                "lahfsahf",
+            ],
+        );
+    }
+
+    #[test]
+    fn recorded_zen2() {
+        assert_features(
+            dumps::ZEN2,
+            &[
+                "mmx", "sse", "sse2", "sse3", "ssse3", "sse4.1", "sse4.2",
+                "sse4a", "avx", "avx2", "fma", "f16c", "bmi", "bmi2", "abm",
+                "popcnt", "fxsr", "xsave", "xsaveopt", "xsaves", "xsavec",
+                "aes", "pclmulqdq", "sha", "rdrand", "rdseed", "adx",
+                "movbe", "lzcnt", "cmpxchg16b",
+                "lahfsahf",
+            ],
+        );
+    }
+
+    #[test]
+    fn recorded_goldmont() {
+        assert_features(
+            dumps::GOLDMONT,
+            &[
+                "mmx", "sse", "sse2", "sse3", "ssse3", "sse4.1", "sse4.2",
+                "popcnt", "fxsr", "aes", "pclmulqdq", "sha", "rdrand",
+                "rdseed", "movbe", "cmpxchg16b",
+                "lahfsahf",
+            ],
+        );
+    }
+
+    #[test]
+    fn recorded_vm_without_avx() {
+        // With the AVX state disabled in XCR0, the VEX and EVEX encoded
+        // features are missing but the legacy encoded ones are still
+        // detected.
+        assert_features(
+            dumps::VM_WITHOUT_AVX,
+            &[
+                "mmx", "sse", "sse2", "sse3", "ssse3", "sse4.1", "sse4.2",
+                "bmi", "bmi2", "abm", "popcnt", "fxsr", "aes", "pclmulqdq",
+                "rdrand", "rdseed", "adx", "movbe", "lzcnt", "cmpxchg16b",
+                "lahfsahf",
+            ],
+        );
+    }
+
+    /// Skylake with every AVX-512 extension up to VP2INTERSECT and BF16.
+    const AVX512_LEAVES: &[(u32, u32, [u32; 4])] = &[
+        (0, 0, [0x16, 0x756e_6547, 0x6c65_746e, 0x4965_6e69]),
+        (1, 0, [0x0005_06e3, 0x0010_0800, 0x7ffa_fbbf, 0xbfeb_fbff]),
+        // EBX: avx512 f, dq, ifma, cd, bw and vl; ECX: vbmi, vbmi2, gfni,
+        // vaes, vpclmulqdq, vnni, bitalg and vpopcntdq; EDX: vp2intersect.
+        (7, 0, [1, 0xd023_0128, 0x0000_5f42, 0x0000_0100]),
+        // EAX: bf16
+        (7, 1, [0x0000_0020, 0, 0, 0]),
+    ];
+
+    fn avx512(xcr0: u64) -> cache::Initializer {
+        detect_features_impl(Dump {
+            leaves: AVX512_LEAVES,
+            xcr0,
+        })
+    }
+
+    fn has(value: cache::Initializer, name: &str) -> bool {
+        let i = FEATURE_NAMES.iter().position(|&n| n == name).unwrap();
+        value.test(i as u32)
+    }
+
+    const AVX512: &[&str] = &[
+        "avx512f",
+        "avx512dq",
+        "avx512ifma",
+        "avx512cd",
+        "avx512bw",
+        "avx512vl",
+        "avx512vbmi",
+        "avx512vpopcntdq",
+        "avx512vbmi2",
+        "avx512vnni",
+        "avx512bitalg",
+        "avx512vp2intersect",
+        "avx512bf16",
+    ];
+
+    #[test]
+    fn recorded_avx512() {
+        let value = avx512(0xe7);
+        for name in AVX512.iter().chain(&["gfni", "vaes", "vpclmulqdq"]) {
+            assert!(has(value, name), "{}", name);
+        }
+
+        // Each of the opmask, ZMM_Hi256 and Hi16_ZMM states is required
+        // for AVX-512, but not for the 256-bit VAES and VPCLMULQDQ.
+        for &xcr0 in &[0xc7, 0xa7, 0x67, 0x07] {
+            let value = avx512(xcr0);
+            for name in AVX512 {
+                assert!(!has(value, name), "{} with {:#x}", name, xcr0);
+            }
+            assert!(has(value, "avx2"));
+            assert!(has(value, "vaes"));
+            assert!(has(value, "vpclmulqdq"));
+            assert!(has(value, "gfni"));
+        }
+
+        // GFNI also has a legacy SSE encoding.
+        let value = avx512(0x03);
+        assert!(!has(value, "vaes"));
+        assert!(!has(value, "vpclmulqdq"));
+        assert!(has(value, "gfni"));
     }
 
     #[test]
@@ -15,12 +15,16 @@ extern "platform-intrinsic" {
     pub fn simd_shuffle8<T, U>(x: T, y: T, idx: [u32; 8]) -> U;
     pub fn simd_shuffle16<T, U>(x: T, y: T, idx: [u32; 16]) -> U;
     pub fn simd_shuffle32<T, U>(x: T, y: T, idx: [u32; 32]) -> U;
+    pub fn simd_shuffle64<T, U>(x: T, y: T, idx: [u32; 64]) -> U;
 
     pub fn simd_insert<T, U>(x: T, idx: u32, val: U) -> T;
     pub fn simd_extract<T, U>(x: T, idx: u32) -> U;
 
     pub fn simd_cast<T, U>(x: T) -> U;
 
+    pub fn simd_gather<T, U, V>(values: T, pointers: U, mask: V) -> T;
+    pub fn simd_scatter<T, U, V>(values: T, pointers: U, mask: V);
+
     pub fn simd_add<T>(x: T, y: T) -> T;
     pub fn simd_sub<T>(x: T, y: T) -> T;
     pub fn simd_mul<T>(x: T, y: T) -> T;
@@ -42,6 +42,33 @@ define_impl! {
     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15
 }
 
+define_ty! { u128x1, u128 }
+define_impl! { u128x1, u128, 1, i128x1, x0 }
+
+define_ty! { i128x1, i128 }
+define_impl! { i128x1, i128, 1, i128x1, x0 }
+
+define_ty_doc! {
+    f16x8, u16, u16, u16, u16, u16, u16, u16, u16 |
+    /// A 128-bit vector with 8 half-precision (IEEE 754 binary16) lanes,
+    /// stored as their bit patterns.
+    ///
+    /// `PartialEq` compares the bit patterns; convert the lanes with
+    /// `to_f32` to compare their values.
+}
+
+define_ty_doc! {
+    c32x2, f32, f32, f32, f32 |
+    /// A 128-bit vector with 2 single-precision complex lanes, stored
+    /// interleaved as `[re, im, re, im]`.
+}
+
+define_ty_doc! {
+    c64x1, f64, f64 |
+    /// A 128-bit vector with 1 double-precision complex lane, stored
+    /// interleaved as `[re, im]`.
+}
+
 define_ty_doc! {
     __m128i, i64, i64 |
     /// 128-bit wide signed integer vector type
@@ -56,6 +83,8 @@ define_from!(
     i16x8,
     u8x16,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -67,6 +96,8 @@ define_from!(
     i16x8,
     u8x16,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -78,6 +109,8 @@ define_from!(
     i16x8,
     u8x16,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -89,6 +122,8 @@ define_from!(
     i16x8,
     u8x16,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -100,6 +135,8 @@ define_from!(
     i16x8,
     u8x16,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -111,6 +148,8 @@ define_from!(
     u16x8,
     u8x16,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -122,6 +161,8 @@ define_from!(
     u16x8,
     i16x8,
     i8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -133,6 +174,8 @@ define_from!(
     u16x8,
     i16x8,
     u8x16,
+    u128x1,
+    i128x1,
     __m128i
 );
 define_from!(
@@ -144,9 +187,90 @@ define_from!(
     i32x4,
     u16x8,
     i16x8,
-    u8x16
+    u8x16,
+    u128x1,
+    i128x1
+);
+define_from!(
+    u128x1,
+    u64x2,
+    i64x2,
+    u32x4,
+    i32x4,
+    u16x8,
+    i16x8,
+    u8x16,
+    i8x16,
+    i128x1,
+    __m128i
+);
+define_from!(
+    i128x1,
+    u64x2,
+    i64x2,
+    u32x4,
+    i32x4,
+    u16x8,
+    i16x8,
+    u8x16,
+    i8x16,
+    u128x1,
+    __m128i
 );
 
+define_array_traits!(
+    (f64x2, f64, 2),
+    (f32x4, f32, 4),
+    (u64x2, u64, 2),
+    (i64x2, i64, 2),
+    (u32x4, u32, 4),
+    (i32x4, i32, 4),
+    (u16x8, u16, 8),
+    (i16x8, i16, 8),
+    (u8x16, u8, 16),
+    (i8x16, i8, 16),
+    (u128x1, u128, 1),
+    (i128x1, i128, 1)
+);
+define_integer_traits!(
+    u64x2,
+    i64x2,
+    u32x4,
+    i32x4,
+    u16x8,
+    i16x8,
+    u8x16,
+    i8x16,
+    u128x1,
+    i128x1
+);
+define_serde!(
+    (f64x2, f64, 2),
+    (f32x4, f32, 4),
+    (u64x2, u64, 2),
+    (i64x2, i64, 2),
+    (u32x4, u32, 4),
+    (i32x4, i32, 4),
+    (u16x8, u16, 8),
+    (i16x8, i16, 8),
+    (u8x16, u8, 16),
+    (i8x16, i8, 16),
+    (u128x1, u128, 1),
+    (i128x1, i128, 1)
+);
+define_float_fmt!(f64x2, f32x4);
+define_integer_fmt!(
+    u64x2,
+    i64x2,
+    u32x4,
+    i32x4,
+    u16x8,
+    i16x8,
+    u8x16,
+    i8x16,
+    u128x1,
+    i128x1
+);
 define_common_ops!(
     f64x2,
     f32x4,
@@ -168,9 +292,175 @@ define_integer_ops!(
     (u16x8, u16),
     (i16x8, i16),
     (u8x16, u8),
-    (i8x16, i8)
+    (i8x16, i8),
+    (u128x1, u128),
+    (i128x1, i128)
 );
+define_byte_shifts!((u128x1, u128x1), (i128x1, u128x1));
 define_signed_integer_ops!(i64x2, i32x4, i16x8, i8x16);
+define_abs_diff!(
+    (u64x2, u64x2),
+    (i64x2, u64x2),
+    (u32x4, u32x4),
+    (i32x4, u32x4),
+    (u16x8, u16x8),
+    (i16x8, u16x8),
+    (u8x16, u8x16),
+    (i8x16, u8x16)
+);
+define_sad_u8!(u8x16, u64x2);
+define_avg!(
+    (u64x2),
+    (u32x4),
+    (u16x8, x86("sse2"): _mm_avg_epu16,
+     arm("neon"): vrhaddq_u16, vhaddq_u16),
+    (u8x16, x86("sse2"): _mm_avg_epu8,
+     arm("neon"): vrhaddq_u8, vhaddq_u8)
+);
+define_mul_q15_round!(
+    (i16x8, x86("ssse3"): _mm_mulhrs_epi16,
+     arm("neon"): vqrdmulhq_s16)
+);
+define_scan!(
+    simd_shuffle2, 2,
+    [0, 1],
+    [1]:
+    (f64x2, f64),
+    (u64x2, u64),
+    (i64x2, i64)
+);
+define_scan!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [1, 2]:
+    (f32x4, f32),
+    (u32x4, u32),
+    (i32x4, i32)
+);
+define_scan!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [1, 2, 4]:
+    (u16x8, u16),
+    (i16x8, i16)
+);
+define_scan!(
+    simd_shuffle16, 16,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [1, 2, 4, 8]:
+    (u8x16, u8),
+    (i8x16, i8)
+);
+define_compress!(
+    permute_4x32, COMPRESS_4, EXPAND_4:
+    (f32x4, f32, i32x4),
+    (u32x4, u32, i32x4),
+    (i32x4, i32, i32x4)
+);
+define_compress!(
+    permute_8x16, COMPRESS_8, EXPAND_8:
+    (u16x8, u16, i16x8),
+    (i16x8, i16, i16x8)
+);
+define_compress!(
+    portable:
+    (f64x2, f64, i64x2),
+    (u64x2, u64, i64x2),
+    (i64x2, i64, i64x2),
+    (u8x16, u8, i8x16),
+    (i8x16, i8, i8x16)
+);
+define_min_max!(
+    f64x2,
+    f32x4,
+    u64x2,
+    i64x2,
+    u32x4,
+    i32x4,
+    u16x8,
+    i16x8,
+    u8x16,
+    i8x16
+);
+define_sort!(
+    simd_shuffle2, 2,
+    [0, 1],
+    [(2, 1)],
+    [(2, 1)]:
+    (f64x2, f64, ::core::f64::INFINITY),
+    (u64x2, u64, ::core::u64::MAX),
+    (i64x2, i64, ::core::i64::MAX)
+);
+define_sort!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [(2, 1), (4, 2), (4, 1)],
+    [(4, 2), (4, 1)]:
+    (f32x4, f32, ::core::f32::INFINITY),
+    (u32x4, u32, ::core::u32::MAX),
+    (i32x4, i32, ::core::i32::MAX)
+);
+define_sort!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1)],
+    [(8, 4), (8, 2), (8, 1)]:
+    (u16x8, u16, ::core::u16::MAX),
+    (i16x8, i16, ::core::i16::MAX)
+);
+define_sort!(
+    simd_shuffle16, 16,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1), (16, 8), (16, 4), (16, 2),
+     (16, 1)],
+    [(16, 8), (16, 4), (16, 2), (16, 1)]:
+    (u8x16, u8, ::core::u8::MAX),
+    (i8x16, i8, ::core::i8::MAX)
+);
+define_transpose4x4!(f32x4, u32x4, i32x4);
+define_transpose8x8!(u16x8, i16x8);
+define_complex!(
+    (c32x2, f32x4, mul_f32x4, conj_f32x4, mul_i_f32x4),
+    (c64x1, f64x2, mul_f64x2, conj_f64x2, mul_i_f64x2)
+);
+define_f16!((f16x8, u16x8, f32x8, widen_x8, narrow_x8));
+define_serde_via!(
+    (f16x8, u16x8, to_bits, from_bits),
+    (c32x2, f32x4, to_interleaved, from_interleaved),
+    (c64x1, f64x2, to_interleaved, from_interleaved)
+);
+define_byte_order!(
+    (u16x8, u8x16, "llvm.bswap.v8i16",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+     arm("neon"): vrev16q_u8),
+    (i16x8, u8x16, "llvm.bswap.v8i16",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14],
+     arm("neon"): vrev16q_u8),
+    (u32x4, u8x16, "llvm.bswap.v4i32",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12],
+     arm("neon"): vrev32q_u8),
+    (i32x4, u8x16, "llvm.bswap.v4i32",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12],
+     arm("neon"): vrev32q_u8),
+    (u64x2, u8x16, "llvm.bswap.v2i64",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8],
+     arm("neon"): vrev64q_u8),
+    (i64x2, u8x16, "llvm.bswap.v2i64",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8],
+     arm("neon"): vrev64q_u8),
+    (u128x1, u8x16, "llvm.bswap.v1i128",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]),
+    (i128x1, u8x16, "llvm.bswap.v1i128",
+     x86("ssse3"): _mm_shuffle_epi8,
+     [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0])
+);
 define_casts!(
     (f64x2, f32x2, as_f32x2),
     (f64x2, u64x2, as_u64x2),
@@ -196,10 +486,238 @@ define_casts!(
 mod tests {
     use super::*;
 
+    #[test]
+    fn byte_order() {
+        test_byte_order!(
+            (u16x8, u16, u8x16),
+            (i16x8, i16, u8x16),
+            (u32x4, u32, u8x16),
+            (i32x4, i32, u8x16),
+            (u64x2, u64, u8x16),
+            (i64x2, i64, u8x16),
+            (u128x1, u128, u8x16),
+            (i128x1, i128, u8x16)
+        );
+    }
+
     #[test]
     fn operators() {
         test_ops_si!(i8x16, i16x8, i32x4, i64x2);
         test_ops_ui!(u8x16, u16x8, u32x4, u64x2);
         test_ops_f!(f32x4, f64x2);
     }
+
+    #[test]
+    fn transpose() {
+        test_transpose!(
+            transpose4x4, 4,
+            (f32x4, f32),
+            (u32x4, u32),
+            (i32x4, i32)
+        );
+        test_transpose!(transpose8x8, 8, (u16x8, u16), (i16x8, i16));
+    }
+
+    #[test]
+    fn complex() {
+        test_complex!((c32x2, f32x4, f32), (c64x1, f64x2, f64));
+    }
+
+    #[test]
+    fn f16() {
+        test_f16!((f16x8, u16x8, f32x8));
+    }
+
+    #[test]
+    fn u128_lanes() {
+        test_bit_arithmetic_!(u128x1);
+        test_bit_arithmetic_!(i128x1);
+        test_byte_shifts!((u128x1, u128), (i128x1, i128));
+
+        let a = u64x2::new(1, 2);
+        let b = u128x1::from(a);
+        assert_eq!(u64x2::from(b), a);
+        assert_eq!(u8x16::from(i128x1::from(b)), u8x16::from(a));
+        #[cfg(target_endian = "little")]
+        assert_eq!(b.extract(0), 1 | 2 << 64);
+        assert_eq!(i128x1::splat(-1).eq(i128x1::splat(-1)), i128x1::splat(-1));
+
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"),
+                  target_feature = "sse2"))]
+        unsafe {
+            let x = u128x1::new(0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100);
+            let l = ::vendor::_mm_slli_si128(i8x16::from(x), 3);
+            let r = ::vendor::_mm_srli_si128(i8x16::from(x), 5);
+            assert_eq!(u128x1::from(l), x.shl_bytes(3));
+            assert_eq!(u128x1::from(r), x.shr_bytes(5));
+        }
+    }
+
+    #[test]
+    fn abs_diff() {
+        test_abs_diff!(
+            (i8x16, i8, u8),
+            (u8x16, u8, u8),
+            (i16x8, i16, u16),
+            (u16x8, u16, u16),
+            (i32x4, i32, u32),
+            (u32x4, u32, u32),
+            (i64x2, i64, u64),
+            (u64x2, u64, u64)
+        );
+    }
+
+    #[test]
+    fn sad_u8() {
+        test_sad_u8!((u8x16, u64x2));
+    }
+
+    #[test]
+    fn avg() {
+        test_avg!(
+            (u8x16, u8),
+            (u16x8, u16),
+            (u32x4, u32),
+            (u64x2, u64)
+        );
+    }
+
+    #[test]
+    fn mul_q15_round() {
+        test_mul_q15_round!(i16x8);
+    }
+
+    #[test]
+    fn scan() {
+        test_scan!(
+            (f64x2, f64),
+            (f32x4, f32),
+            (u64x2, u64),
+            (i64x2, i64),
+            (u32x4, u32),
+            (i32x4, i32),
+            (u16x8, u16),
+            (i16x8, i16),
+            (u8x16, u8),
+            (i8x16, i8)
+        );
+    }
+
+    #[test]
+    fn compress() {
+        test_compress!(
+            (f32x4, f32, i32x4),
+            (u32x4, u32, i32x4),
+            (i32x4, i32, i32x4),
+            (u16x8, u16, i16x8),
+            (i16x8, i16, i16x8),
+            (f64x2, f64, i64x2),
+            (u64x2, u64, i64x2),
+            (i64x2, i64, i64x2),
+            (u8x16, u8, i8x16),
+            (i8x16, i8, i8x16)
+        );
+    }
+
+    #[test]
+    fn sort() {
+        test_sort!(
+            (f64x2, f64),
+            (f32x4, f32),
+            (u64x2, u64),
+            (i64x2, i64),
+            (u32x4, u32),
+            (i32x4, i32),
+            (u16x8, u16),
+            (i16x8, i16),
+            (u8x16, u8),
+            (i8x16, i8)
+        );
+        test_sort!(nan: (f64x2, f64), (f32x4, f32));
+    }
+
+    #[test]
+    fn array_traits() {
+        test_array_traits!(
+            (f64x2, f64, 2),
+            (f32x4, f32, 4),
+            (u64x2, u64, 2),
+            (i64x2, i64, 2),
+            (u32x4, u32, 4),
+            (i32x4, i32, 4),
+            (u16x8, u16, 8),
+            (i16x8, i16, 8),
+            (u8x16, u8, 16),
+            (i8x16, i8, 16),
+            (u128x1, u128, 1),
+            (i128x1, i128, 1)
+        );
+        test_from_iter_len!(
+            (f64x2, f64, 2),
+            (f32x4, f32, 4),
+            (u64x2, u64, 2),
+            (i64x2, i64, 2),
+            (u32x4, u32, 4),
+            (i32x4, i32, 4),
+            (u16x8, u16, 8),
+            (i16x8, i16, 8),
+            (u8x16, u8, 16),
+            (i8x16, i8, 16),
+            (u128x1, u128, 1),
+            (i128x1, i128, 1)
+        );
+        test_hash!(
+            u64x2, i64x2, u32x4, i32x4, u16x8, i16x8, u8x16, i8x16, u128x1,
+            i128x1
+        );
+    }
+
+    #[test]
+    fn fmt() {
+        assert_eq!(format!("{}", i32x4::new(1, -2, 3, 4)), "[1, -2, 3, 4]");
+        assert_eq!(
+            format!("{:#04x}", u16x8::new(1, 2, 3, 4, 5, 6, 7, 255)),
+            "[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0xff]"
+        );
+        assert_eq!(
+            format!("{:04b}", u16x8::new(0, 1, 2, 3, 4, 5, 6, 7)),
+            "[0000, 0001, 0010, 0011, 0100, 0101, 0110, 0111]"
+        );
+        assert_eq!(format!("{:.2}", f64x2::new(0.5, -1.)), "[0.50, -1.00]");
+        test_float_fmt!((f64x2, f64), (f32x4, f32));
+        test_integer_fmt!(
+            (u64x2, u64),
+            (i64x2, i64),
+            (u32x4, u32),
+            (i32x4, i32),
+            (u16x8, u16),
+            (i16x8, i16),
+            (u8x16, u8),
+            (i8x16, i8)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        test_serde!(
+            (f64x2, f64),
+            (f32x4, f32),
+            (u64x2, u64),
+            (i64x2, i64),
+            (u32x4, u32),
+            (i32x4, i32),
+            (u16x8, u16),
+            (i16x8, i16),
+            (u8x16, u8),
+            (i8x16, i8),
+            (u128x1, u128),
+            (i128x1, i128)
+        );
+        test_serde_via!(
+            (f16x8, u16x8, u16, to_bits, from_bits),
+            (c32x2, f32x4, f32, to_interleaved, from_interleaved),
+            (c64x1, f64x2, f64, to_interleaved, from_interleaved)
+        );
+    }
 }
@@ -0,0 +1,54 @@
+//! 16-bit wide vector types
+
+use simd_llvm::*;
+
+define_ty! { i8x2, i8, i8 }
+define_impl! { i8x2, i8, 2, i8x2, x0, x1 }
+define_ty! { u8x2, u8, u8 }
+define_impl! { u8x2, u8, 2, i8x2, x0, x1 }
+
+define_array_traits!((i8x2, i8, 2), (u8x2, u8, 2));
+define_integer_traits!(i8x2, u8x2);
+define_serde!((i8x2, i8, 2), (u8x2, u8, 2));
+define_integer_fmt!(i8x2, u8x2);
+
+define_common_ops!(i8x2, u8x2);
+define_integer_ops!((i8x2, i8), (u8x2, u8));
+define_signed_integer_ops!(i8x2);
+define_abs_diff!((u8x2, u8x2), (i8x2, u8x2));
+define_scan!(
+    simd_shuffle2, 2,
+    [0, 1],
+    [1]:
+    (u8x2, u8),
+    (i8x2, i8)
+);
+define_compress!(portable: (u8x2, u8, i8x2), (i8x2, i8, i8x2));
+
+define_casts!((i8x2, i64x2, as_i64x2), (u8x2, i64x2, as_i64x2));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        test_ops_si!(i8x2);
+        test_ops_ui!(u8x2);
+    }
+
+    #[test]
+    fn abs_diff() {
+        test_abs_diff!((i8x2, i8, u8), (u8x2, u8, u8));
+    }
+
+    #[test]
+    fn scan() {
+        test_scan!((u8x2, u8), (i8x2, i8));
+    }
+
+    #[test]
+    fn compress() {
+        test_compress!((u8x2, u8, i8x2), (i8x2, i8, i8x2));
+    }
+}
@@ -66,6 +66,35 @@ define_impl! {
     x24, x25, x26, x27, x28, x29, x30, x31
 }
 
+define_ty! { u128x2, u128, u128 }
+define_impl! { u128x2, u128, 2, i128x2, x0, x1 }
+
+define_ty! { i128x2, i128, i128 }
+define_impl! { i128x2, i128, 2, i128x2, x0, x1 }
+
+define_ty_doc! {
+    f16x16,
+    u16, u16, u16, u16, u16, u16, u16, u16,
+    u16, u16, u16, u16, u16, u16, u16, u16 |
+    /// A 256-bit vector with 16 half-precision (IEEE 754 binary16) lanes,
+    /// stored as their bit patterns.
+    ///
+    /// `PartialEq` compares the bit patterns; convert the lanes with
+    /// `to_f32` to compare their values.
+}
+
+define_ty_doc! {
+    c32x4, f32, f32, f32, f32, f32, f32, f32, f32 |
+    /// A 256-bit vector with 4 single-precision complex lanes, stored
+    /// interleaved as `[re, im, re, im, ...]`.
+}
+
+define_ty_doc! {
+    c64x2, f64, f64, f64, f64 |
+    /// A 256-bit vector with 2 double-precision complex lanes, stored
+    /// interleaved as `[re, im, re, im]`.
+}
+
 define_ty_doc! {
     __m256i,
     i64, i64, i64, i64 |
@@ -81,6 +110,8 @@ define_from!(
     i16x16,
     u8x32,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -92,6 +123,8 @@ define_from!(
     i16x16,
     u8x32,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -103,6 +136,8 @@ define_from!(
     i16x16,
     u8x32,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -114,6 +149,8 @@ define_from!(
     i16x16,
     u8x32,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -125,6 +162,8 @@ define_from!(
     i16x16,
     u8x32,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -136,6 +175,8 @@ define_from!(
     u16x16,
     u8x32,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -147,6 +188,8 @@ define_from!(
     u16x16,
     i16x16,
     i8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -158,6 +201,8 @@ define_from!(
     u16x16,
     i16x16,
     u8x32,
+    u128x2,
+    i128x2,
     __m256i
 );
 define_from!(
@@ -169,9 +214,90 @@ define_from!(
     i32x8,
     u16x16,
     i16x16,
-    u8x32
+    u8x32,
+    u128x2,
+    i128x2
+);
+define_from!(
+    u128x2,
+    u64x4,
+    i64x4,
+    u32x8,
+    i32x8,
+    u16x16,
+    i16x16,
+    u8x32,
+    i8x32,
+    i128x2,
+    __m256i
+);
+define_from!(
+    i128x2,
+    u64x4,
+    i64x4,
+    u32x8,
+    i32x8,
+    u16x16,
+    i16x16,
+    u8x32,
+    i8x32,
+    u128x2,
+    __m256i
 );
 
+define_array_traits!(
+    (f64x4, f64, 4),
+    (f32x8, f32, 8),
+    (u64x4, u64, 4),
+    (i64x4, i64, 4),
+    (u32x8, u32, 8),
+    (i32x8, i32, 8),
+    (u16x16, u16, 16),
+    (i16x16, i16, 16),
+    (u8x32, u8, 32),
+    (i8x32, i8, 32),
+    (u128x2, u128, 2),
+    (i128x2, i128, 2)
+);
+define_integer_traits!(
+    u64x4,
+    i64x4,
+    u32x8,
+    i32x8,
+    u16x16,
+    i16x16,
+    u8x32,
+    i8x32,
+    u128x2,
+    i128x2
+);
+define_serde!(
+    (f64x4, f64, 4),
+    (f32x8, f32, 8),
+    (u64x4, u64, 4),
+    (i64x4, i64, 4),
+    (u32x8, u32, 8),
+    (i32x8, i32, 8),
+    (u16x16, u16, 16),
+    (i16x16, i16, 16),
+    (u8x32, u8, 32),
+    (i8x32, i8, 32),
+    (u128x2, u128, 2),
+    (i128x2, i128, 2)
+);
+define_float_fmt!(f64x4, f32x8);
+define_integer_fmt!(
+    u64x4,
+    i64x4,
+    u32x8,
+    i32x8,
+    u16x16,
+    i16x16,
+    u8x32,
+    i8x32,
+    u128x2,
+    i128x2
+);
 define_common_ops!(
     f64x4,
     f32x8,
@@ -193,9 +319,171 @@ define_integer_ops!(
     (u16x16, u16),
     (i16x16, i16),
     (u8x32, u8),
-    (i8x32, i8)
+    (i8x32, i8),
+    (u128x2, u128),
+    (i128x2, i128)
 );
+define_byte_shifts!((u128x2, u128x2), (i128x2, u128x2));
 define_signed_integer_ops!(i64x4, i32x8, i16x16, i8x32);
+define_abs_diff!(
+    (u64x4, u64x4),
+    (i64x4, u64x4),
+    (u32x8, u32x8),
+    (i32x8, u32x8),
+    (u16x16, u16x16),
+    (i16x16, u16x16),
+    (u8x32, u8x32),
+    (i8x32, u8x32)
+);
+define_sad_u8!(u8x32, u64x4);
+define_avg!(
+    (u64x4),
+    (u32x8),
+    (u16x16, x86("avx2"): _mm256_avg_epu16),
+    (u8x32, x86("avx2"): _mm256_avg_epu8)
+);
+define_mul_q15_round!((i16x16, x86("avx2"): _mm256_mulhrs_epi16));
+define_scan!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [1, 2]:
+    (f64x4, f64),
+    (u64x4, u64),
+    (i64x4, i64)
+);
+define_scan!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [1, 2, 4]:
+    (f32x8, f32),
+    (u32x8, u32),
+    (i32x8, i32)
+);
+define_scan!(
+    simd_shuffle16, 16,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [1, 2, 4, 8]:
+    (u16x16, u16),
+    (i16x16, i16)
+);
+define_scan!(
+    simd_shuffle32, 32,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+     21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+    [1, 2, 4, 8, 16]:
+    (u8x32, u8),
+    (i8x32, i8)
+);
+define_compress!(
+    permute_8x32, COMPRESS_8, EXPAND_8:
+    (f32x8, f32, i32x8),
+    (u32x8, u32, i32x8),
+    (i32x8, i32, i32x8)
+);
+define_compress!(
+    portable:
+    (f64x4, f64, i64x4),
+    (u64x4, u64, i64x4),
+    (i64x4, i64, i64x4),
+    (u16x16, u16, i16x16),
+    (i16x16, i16, i16x16),
+    (u8x32, u8, i8x32),
+    (i8x32, i8, i8x32)
+);
+define_min_max!(
+    f64x4,
+    f32x8,
+    u64x4,
+    i64x4,
+    u32x8,
+    i32x8,
+    u16x16,
+    i16x16,
+    u8x32,
+    i8x32
+);
+define_sort!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [(2, 1), (4, 2), (4, 1)],
+    [(4, 2), (4, 1)]:
+    (f64x4, f64, ::core::f64::INFINITY),
+    (u64x4, u64, ::core::u64::MAX),
+    (i64x4, i64, ::core::i64::MAX)
+);
+define_sort!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1)],
+    [(8, 4), (8, 2), (8, 1)]:
+    (f32x8, f32, ::core::f32::INFINITY),
+    (u32x8, u32, ::core::u32::MAX),
+    (i32x8, i32, ::core::i32::MAX)
+);
+define_sort!(
+    simd_shuffle16, 16,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1), (16, 8), (16, 4), (16, 2),
+     (16, 1)],
+    [(16, 8), (16, 4), (16, 2), (16, 1)]:
+    (u16x16, u16, ::core::u16::MAX),
+    (i16x16, i16, ::core::i16::MAX)
+);
+define_sort!(
+    simd_shuffle32, 32,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+     21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1), (16, 8), (16, 4), (16, 2),
+     (16, 1), (32, 16), (32, 8), (32, 4), (32, 2), (32, 1)],
+    [(32, 16), (32, 8), (32, 4), (32, 2), (32, 1)]:
+    (u8x32, u8, ::core::u8::MAX),
+    (i8x32, i8, ::core::i8::MAX)
+);
+define_transpose4x4!(f64x4, u64x4, i64x4);
+define_complex!(
+    (c32x4, f32x8, mul_f32x8, conj_f32x8, mul_i_f32x8),
+    (c64x2, f64x4, mul_f64x4, conj_f64x4, mul_i_f64x4)
+);
+define_f16!((f16x16, u16x16, f32x16, widen_x16, narrow_x16));
+define_serde_via!(
+    (f16x16, u16x16, to_bits, from_bits),
+    (c32x4, f32x8, to_interleaved, from_interleaved),
+    (c64x2, f64x4, to_interleaved, from_interleaved)
+);
+define_byte_order!(
+    (u16x16, u8x32, "llvm.bswap.v16i16",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18,
+      21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30]),
+    (i16x16, u8x32, "llvm.bswap.v16i16",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 17, 16, 19, 18,
+      21, 20, 23, 22, 25, 24, 27, 26, 29, 28, 31, 30]),
+    (u32x8, u8x32, "llvm.bswap.v8i32",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12, 19, 18, 17, 16,
+      23, 22, 21, 20, 27, 26, 25, 24, 31, 30, 29, 28]),
+    (i32x8, u8x32, "llvm.bswap.v8i32",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12, 19, 18, 17, 16,
+      23, 22, 21, 20, 27, 26, 25, 24, 31, 30, 29, 28]),
+    (u64x4, u8x32, "llvm.bswap.v4i64",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 23, 22, 21, 20,
+      19, 18, 17, 16, 31, 30, 29, 28, 27, 26, 25, 24]),
+    (i64x4, u8x32, "llvm.bswap.v4i64",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 23, 22, 21, 20,
+      19, 18, 17, 16, 31, 30, 29, 28, 27, 26, 25, 24]),
+    (u128x2, u8x32, "llvm.bswap.v2i128",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 31, 30, 29, 28,
+      27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16]),
+    (i128x2, u8x32, "llvm.bswap.v2i128",
+     x86("avx2"): _mm256_shuffle_epi8,
+     [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 31, 30, 29, 28,
+      27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16])
+);
 define_casts!(
     (f64x4, f32x4, as_f32x4),
     (f64x4, u64x4, as_u64x4),
@@ -220,10 +508,216 @@ define_casts!(
 mod tests {
     use super::*;
 
+    #[test]
+    fn byte_order() {
+        test_byte_order!(
+            (u16x16, u16, u8x32),
+            (i16x16, i16, u8x32),
+            (u32x8, u32, u8x32),
+            (i32x8, i32, u8x32),
+            (u64x4, u64, u8x32),
+            (i64x4, i64, u8x32),
+            (u128x2, u128, u8x32),
+            (i128x2, i128, u8x32)
+        );
+    }
+
     #[test]
     fn operators() {
         test_ops_si!(i8x32, i16x16, i32x8, i64x4);
         test_ops_ui!(u8x32, u16x16, u32x8, u64x4);
         test_ops_f!(f32x8, f64x4);
     }
+
+    #[test]
+    fn transpose() {
+        test_transpose!(
+            transpose4x4, 4,
+            (f64x4, f64),
+            (u64x4, u64),
+            (i64x4, i64)
+        );
+    }
+
+    #[test]
+    fn complex() {
+        test_complex!((c32x4, f32x8, f32), (c64x2, f64x4, f64));
+    }
+
+    #[test]
+    fn f16() {
+        test_f16!((f16x16, u16x16, f32x16));
+    }
+
+    #[test]
+    fn u128_lanes() {
+        test_bit_arithmetic_!(u128x2);
+        test_bit_arithmetic_!(i128x2);
+        test_byte_shifts!((u128x2, u128), (i128x2, i128));
+
+        let a = u64x4::new(1, 2, 3, 4);
+        let b = u128x2::from(a);
+        assert_eq!(u64x4::from(b), a);
+        assert_eq!(u8x32::from(i128x2::from(b)), u8x32::from(a));
+        #[cfg(target_endian = "little")]
+        assert_eq!(b, u128x2::new(1 | 2 << 64, 3 | 4 << 64));
+    }
+
+    #[test]
+    fn abs_diff() {
+        test_abs_diff!(
+            (i8x32, i8, u8),
+            (u8x32, u8, u8),
+            (i16x16, i16, u16),
+            (u16x16, u16, u16),
+            (i32x8, i32, u32),
+            (u32x8, u32, u32),
+            (i64x4, i64, u64),
+            (u64x4, u64, u64)
+        );
+    }
+
+    #[test]
+    fn sad_u8() {
+        test_sad_u8!((u8x32, u64x4));
+    }
+
+    #[test]
+    fn avg() {
+        test_avg!(
+            (u8x32, u8),
+            (u16x16, u16),
+            (u32x8, u32),
+            (u64x4, u64)
+        );
+    }
+
+    #[test]
+    fn mul_q15_round() {
+        test_mul_q15_round!(i16x16);
+    }
+
+    #[test]
+    fn scan() {
+        test_scan!(
+            (f64x4, f64),
+            (f32x8, f32),
+            (u64x4, u64),
+            (i64x4, i64),
+            (u32x8, u32),
+            (i32x8, i32),
+            (u16x16, u16),
+            (i16x16, i16),
+            (u8x32, u8),
+            (i8x32, i8)
+        );
+    }
+
+    #[test]
+    fn compress() {
+        test_compress!(
+            (f32x8, f32, i32x8),
+            (u32x8, u32, i32x8),
+            (i32x8, i32, i32x8),
+            (f64x4, f64, i64x4),
+            (u64x4, u64, i64x4),
+            (i64x4, i64, i64x4),
+            (u16x16, u16, i16x16),
+            (i16x16, i16, i16x16),
+            (u8x32, u8, i8x32),
+            (i8x32, i8, i8x32)
+        );
+    }
+
+    #[test]
+    fn sort() {
+        test_sort!(
+            (f64x4, f64),
+            (f32x8, f32),
+            (u64x4, u64),
+            (i64x4, i64),
+            (u32x8, u32),
+            (i32x8, i32),
+            (u16x16, u16),
+            (i16x16, i16),
+            (u8x32, u8),
+            (i8x32, i8)
+        );
+        test_sort!(nan: (f64x4, f64), (f32x8, f32));
+    }
+
+    #[test]
+    fn array_traits() {
+        test_array_traits!(
+            (f64x4, f64, 4),
+            (f32x8, f32, 8),
+            (u64x4, u64, 4),
+            (i64x4, i64, 4),
+            (u32x8, u32, 8),
+            (i32x8, i32, 8),
+            (u16x16, u16, 16),
+            (i16x16, i16, 16),
+            (u8x32, u8, 32),
+            (i8x32, i8, 32),
+            (u128x2, u128, 2),
+            (i128x2, i128, 2)
+        );
+        test_from_iter_len!(
+            (f64x4, f64, 4),
+            (f32x8, f32, 8),
+            (u64x4, u64, 4),
+            (i64x4, i64, 4),
+            (u32x8, u32, 8),
+            (i32x8, i32, 8),
+            (u16x16, u16, 16),
+            (i16x16, i16, 16),
+            (u8x32, u8, 32),
+            (i8x32, i8, 32),
+            (u128x2, u128, 2),
+            (i128x2, i128, 2)
+        );
+        test_hash!(
+            u64x4, i64x4, u32x8, i32x8, u16x16, i16x16, u8x32, i8x32, u128x2,
+            i128x2
+        );
+    }
+
+    #[test]
+    fn fmt() {
+        test_float_fmt!((f64x4, f64), (f32x8, f32));
+        test_integer_fmt!(
+            (u64x4, u64),
+            (i64x4, i64),
+            (u32x8, u32),
+            (i32x8, i32),
+            (u16x16, u16),
+            (i16x16, i16),
+            (u8x32, u8),
+            (i8x32, i8)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        test_serde!(
+            (f64x4, f64),
+            (f32x8, f32),
+            (u64x4, u64),
+            (i64x4, i64),
+            (u32x8, u32),
+            (i32x8, i32),
+            (u16x16, u16),
+            (i16x16, i16),
+            (u8x32, u8),
+            (i8x32, i8),
+            (u128x2, u128),
+            (i128x2, i128)
+        );
+        test_serde_via!(
+            (f16x16, u16x16, u16, to_bits, from_bits),
+            (c32x4, f32x8, f32, to_interleaved, from_interleaved),
+            (c64x2, f64x4, f64, to_interleaved, from_interleaved)
+        );
+    }
 }
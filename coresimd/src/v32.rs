@@ -0,0 +1,116 @@
+//! 32-bit wide vector types
+
+use simd_llvm::*;
+
+define_ty! { i16x2, i16, i16 }
+define_impl! { i16x2, i16, 2, i16x2, x0, x1 }
+define_ty! { u16x2, u16, u16 }
+define_impl! { u16x2, u16, 2, i16x2, x0, x1 }
+
+define_ty! { i8x4, i8, i8, i8, i8 }
+define_impl! { i8x4, i8, 4, i8x4, x0, x1, x2, x3 }
+define_ty! { u8x4, u8, u8, u8, u8 }
+define_impl! { u8x4, u8, 4, i8x4, x0, x1, x2, x3 }
+
+define_from!(u16x2, i16x2, u8x4, i8x4);
+define_from!(i16x2, u16x2, u8x4, i8x4);
+define_from!(u8x4, u16x2, i16x2, i8x4);
+define_from!(i8x4, u16x2, i16x2, u8x4);
+
+define_array_traits!(
+    (i16x2, i16, 2),
+    (u16x2, u16, 2),
+    (i8x4, i8, 4),
+    (u8x4, u8, 4)
+);
+define_integer_traits!(i16x2, u16x2, i8x4, u8x4);
+define_serde!(
+    (i16x2, i16, 2),
+    (u16x2, u16, 2),
+    (i8x4, i8, 4),
+    (u8x4, u8, 4)
+);
+define_integer_fmt!(i16x2, u16x2, i8x4, u8x4);
+
+define_common_ops!(i16x2, u16x2, i8x4, u8x4);
+define_integer_ops!((i16x2, i16), (u16x2, u16), (i8x4, i8), (u8x4, u8));
+define_signed_integer_ops!(i16x2, i8x4);
+define_abs_diff!(
+    (u16x2, u16x2),
+    (i16x2, u16x2),
+    (u8x4, u8x4),
+    (i8x4, u8x4)
+);
+define_scan!(
+    simd_shuffle2, 2,
+    [0, 1],
+    [1]:
+    (u16x2, u16),
+    (i16x2, i16)
+);
+define_scan!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [1, 2]:
+    (u8x4, u8),
+    (i8x4, i8)
+);
+define_byte_order!(
+    (u16x2, u8x4, "llvm.bswap.v2i16"),
+    (i16x2, u8x4, "llvm.bswap.v2i16")
+);
+define_compress!(
+    portable:
+    (u16x2, u16, i16x2),
+    (i16x2, i16, i16x2),
+    (u8x4, u8, i8x4),
+    (i8x4, i8, i8x4)
+);
+
+define_casts!(
+    (i16x2, i64x2, as_i64x2),
+    (u16x2, i64x2, as_i64x2),
+    (i8x4, i32x4, as_i32x4),
+    (u8x4, i32x4, as_i32x4)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        test_ops_si!(i8x4, i16x2);
+        test_ops_ui!(u8x4, u16x2);
+    }
+
+    #[test]
+    fn byte_order() {
+        test_byte_order!((u16x2, u16, u8x4), (i16x2, i16, u8x4));
+    }
+
+    #[test]
+    fn abs_diff() {
+        test_abs_diff!(
+            (i8x4, i8, u8),
+            (u8x4, u8, u8),
+            (i16x2, i16, u16),
+            (u16x2, u16, u16)
+        );
+    }
+
+    #[test]
+    fn scan() {
+        test_scan!((u16x2, u16), (i16x2, i16), (u8x4, u8), (i8x4, i8));
+    }
+
+    #[test]
+    fn compress() {
+        test_compress!(
+            (u16x2, u16, i16x2),
+            (i16x2, i16, i16x2),
+            (u8x4, u8, i8x4),
+            (i8x4, i8, i8x4)
+        );
+    }
+}
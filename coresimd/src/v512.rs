@@ -112,15 +112,186 @@ define_impl! {
     x56, x57, x58, x59, x60, x61, x62, x63
 }
 
-define_from!(u64x8, i64x8, u32x16, i32x16, u16x32, i16x32, u8x64, i8x64);
-define_from!(i64x8, u64x8, u32x16, i32x16, u16x32, i16x32, u8x64, i8x64);
-define_from!(u32x16, u64x8, i64x8, i32x16, u16x32, i16x32, u8x64, i8x64);
-define_from!(i32x16, u64x8, i64x8, u32x16, u16x32, i16x32, u8x64, i8x64);
-define_from!(u16x32, u64x8, i64x8, u32x16, i32x16, i16x32, u8x64, i8x64);
-define_from!(i16x32, u64x8, i64x8, u32x16, i32x16, u16x32, u8x64, i8x64);
-define_from!(u8x64, u64x8, i64x8, u32x16, i32x16, u16x32, i16x32, i8x64);
-define_from!(i8x64, u64x8, i64x8, u32x16, i32x16, u16x32, i16x32, u8x64);
+define_ty! { u128x4, u128, u128, u128, u128 }
+define_impl! { u128x4, u128, 4, i128x4, x0, x1, x2, x3 }
 
+define_ty! { i128x4, i128, i128, i128, i128 }
+define_impl! { i128x4, i128, 4, i128x4, x0, x1, x2, x3 }
+
+define_from!(
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    i64x8,
+    u64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    u32x16,
+    u64x8,
+    i64x8,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    i32x16,
+    u64x8,
+    i64x8,
+    u32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    u16x32,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    i16x32,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    u8x64,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    i8x64,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    u128x4,
+    i128x4
+);
+define_from!(
+    u128x4,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    i128x4
+);
+define_from!(
+    i128x4,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4
+);
+
+define_array_traits!(
+    (f64x8, f64, 8),
+    (f32x16, f32, 16),
+    (u64x8, u64, 8),
+    (i64x8, i64, 8),
+    (u32x16, u32, 16),
+    (i32x16, i32, 16),
+    (u16x32, u16, 32),
+    (i16x32, i16, 32),
+    (u8x64, u8, 64),
+    (i8x64, i8, 64),
+    (u128x4, u128, 4),
+    (i128x4, i128, 4)
+);
+define_integer_traits!(
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
+define_serde!(
+    (f64x8, f64, 8),
+    (f32x16, f32, 16),
+    (u64x8, u64, 8),
+    (i64x8, i64, 8),
+    (u32x16, u32, 16),
+    (i32x16, i32, 16),
+    (u16x32, u16, 32),
+    (i16x32, i16, 32),
+    (u8x64, u8, 64),
+    (i8x64, i8, 64),
+    (u128x4, u128, 4),
+    (i128x4, i128, 4)
+);
+define_float_fmt!(f64x8, f32x16);
+define_integer_fmt!(
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64,
+    u128x4,
+    i128x4
+);
 define_common_ops!(
     f64x8,
     f32x16,
@@ -142,9 +313,126 @@ define_integer_ops!(
     (u16x32, u16),
     (i16x32, i16),
     (u8x64, u8),
-    (i8x64, i8)
+    (i8x64, i8),
+    (u128x4, u128),
+    (i128x4, i128)
 );
+define_byte_shifts!((u128x4, u128x4), (i128x4, u128x4));
 define_signed_integer_ops!(i64x8, i32x16, i16x32, i8x64);
+define_abs_diff!(
+    (u64x8, u64x8),
+    (i64x8, u64x8),
+    (u32x16, u32x16),
+    (i32x16, u32x16),
+    (u16x32, u16x32),
+    (i16x32, u16x32),
+    (u8x64, u8x64),
+    (i8x64, u8x64)
+);
+define_sad_u8!(u8x64, u64x8);
+define_avg!((u64x8), (u32x16), (u16x32), (u8x64));
+define_mul_q15_round!((i16x32));
+define_scan!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [1, 2, 4]:
+    (f64x8, f64),
+    (u64x8, u64),
+    (i64x8, i64)
+);
+define_scan!(
+    simd_shuffle16, 16,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [1, 2, 4, 8]:
+    (f32x16, f32),
+    (u32x16, u32),
+    (i32x16, i32)
+);
+define_scan!(
+    simd_shuffle32, 32,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+     21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+    [1, 2, 4, 8, 16]:
+    (u16x32, u16),
+    (i16x32, i16)
+);
+define_scan!(
+    simd_shuffle64, 64,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+     21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38,
+     39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56,
+     57, 58, 59, 60, 61, 62, 63],
+    [1, 2, 4, 8, 16, 32]:
+    (u8x64, u8),
+    (i8x64, i8)
+);
+define_compress!(
+    portable:
+    (f64x8, f64, i64x8),
+    (f32x16, f32, i32x16),
+    (u64x8, u64, i64x8),
+    (i64x8, i64, i64x8),
+    (u32x16, u32, i32x16),
+    (i32x16, i32, i32x16),
+    (u16x32, u16, i16x32),
+    (i16x32, i16, i16x32),
+    (u8x64, u8, i8x64),
+    (i8x64, i8, i8x64)
+);
+define_min_max!(
+    f64x8,
+    f32x16,
+    u64x8,
+    i64x8,
+    u32x16,
+    i32x16,
+    u16x32,
+    i16x32,
+    u8x64,
+    i8x64
+);
+define_sort!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1)],
+    [(8, 4), (8, 2), (8, 1)]:
+    (f64x8, f64, ::core::f64::INFINITY),
+    (u64x8, u64, ::core::u64::MAX),
+    (i64x8, i64, ::core::i64::MAX)
+);
+define_sort!(
+    simd_shuffle16, 16,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1), (16, 8), (16, 4), (16, 2),
+     (16, 1)],
+    [(16, 8), (16, 4), (16, 2), (16, 1)]:
+    (f32x16, f32, ::core::f32::INFINITY),
+    (u32x16, u32, ::core::u32::MAX),
+    (i32x16, i32, ::core::i32::MAX)
+);
+define_sort!(
+    simd_shuffle32, 32,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+     21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1), (16, 8), (16, 4), (16, 2),
+     (16, 1), (32, 16), (32, 8), (32, 4), (32, 2), (32, 1)],
+    [(32, 16), (32, 8), (32, 4), (32, 2), (32, 1)]:
+    (u16x32, u16, ::core::u16::MAX),
+    (i16x32, i16, ::core::i16::MAX)
+);
+define_sort!(
+    simd_shuffle64, 64,
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+     21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38,
+     39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56,
+     57, 58, 59, 60, 61, 62, 63],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1), (16, 8), (16, 4), (16, 2),
+     (16, 1), (32, 16), (32, 8), (32, 4), (32, 2), (32, 1), (64, 32), (64, 16),
+     (64, 8), (64, 4), (64, 2), (64, 1)],
+    [(64, 32), (64, 16), (64, 8), (64, 4), (64, 2), (64, 1)]:
+    (u8x64, u8, ::core::u8::MAX),
+    (i8x64, i8, ::core::i8::MAX)
+);
 define_casts!(
     (f64x8, f32x8, as_f32x8),
     (f64x8, u64x8, as_u64x8),
@@ -164,15 +452,209 @@ define_casts!(
     (u8x64, i8x64, as_i8x64),
     (i8x64, u8x64, as_u8x64)
 );
+define_byte_order!(
+    (u16x32, u8x64, "llvm.bswap.v32i16"),
+    (i16x32, u8x64, "llvm.bswap.v32i16"),
+    (u32x16, u8x64, "llvm.bswap.v16i32"),
+    (i32x16, u8x64, "llvm.bswap.v16i32"),
+    (u64x8, u8x64, "llvm.bswap.v8i64"),
+    (i64x8, u8x64, "llvm.bswap.v8i64"),
+    (u128x4, u8x64, "llvm.bswap.v4i128"),
+    (i128x4, u8x64, "llvm.bswap.v4i128")
+);
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn byte_order() {
+        test_byte_order!(
+            (u16x32, u16, u8x64),
+            (i16x32, i16, u8x64),
+            (u32x16, u32, u8x64),
+            (i32x16, i32, u8x64),
+            (u64x8, u64, u8x64),
+            (i64x8, i64, u8x64),
+            (u128x4, u128, u8x64),
+            (i128x4, i128, u8x64)
+        );
+    }
+
     #[test]
     fn operators() {
         test_ops_si!(i8x64, i16x32, i32x16, i64x8);
         test_ops_ui!(u8x64, u16x32, u32x16, u64x8);
         test_ops_f!(f32x16, f64x8);
     }
+
+    #[test]
+    fn u128_lanes() {
+        test_bit_arithmetic_!(u128x4);
+        test_bit_arithmetic_!(i128x4);
+        test_byte_shifts!((u128x4, u128), (i128x4, i128));
+
+        let a = u64x8::new(1, 2, 3, 4, 5, 6, 7, 8);
+        let b = u128x4::from(a);
+        assert_eq!(u64x8::from(b), a);
+        assert_eq!(u32x16::from(i128x4::from(b)), u32x16::from(a));
+        #[cfg(target_endian = "little")]
+        assert_eq!(
+            b,
+            u128x4::new(1 | 2 << 64, 3 | 4 << 64, 5 | 6 << 64, 7 | 8 << 64)
+        );
+    }
+
+    #[test]
+    fn abs_diff() {
+        test_abs_diff!(
+            (i8x64, i8, u8),
+            (u8x64, u8, u8),
+            (i16x32, i16, u16),
+            (u16x32, u16, u16),
+            (i32x16, i32, u32),
+            (u32x16, u32, u32),
+            (i64x8, i64, u64),
+            (u64x8, u64, u64)
+        );
+    }
+
+    #[test]
+    fn sad_u8() {
+        test_sad_u8!((u8x64, u64x8));
+    }
+
+    #[test]
+    fn avg() {
+        test_avg!(
+            (u8x64, u8),
+            (u16x32, u16),
+            (u32x16, u32),
+            (u64x8, u64)
+        );
+    }
+
+    #[test]
+    fn mul_q15_round() {
+        test_mul_q15_round!(i16x32);
+    }
+
+    #[test]
+    fn scan() {
+        test_scan!(
+            (f64x8, f64),
+            (f32x16, f32),
+            (u64x8, u64),
+            (i64x8, i64),
+            (u32x16, u32),
+            (i32x16, i32),
+            (u16x32, u16),
+            (i16x32, i16),
+            (u8x64, u8),
+            (i8x64, i8)
+        );
+    }
+
+    #[test]
+    fn compress() {
+        test_compress!(
+            (f64x8, f64, i64x8),
+            (f32x16, f32, i32x16),
+            (u64x8, u64, i64x8),
+            (i64x8, i64, i64x8),
+            (u32x16, u32, i32x16),
+            (i32x16, i32, i32x16),
+            (u16x32, u16, i16x32),
+            (i16x32, i16, i16x32),
+            (u8x64, u8, i8x64),
+            (i8x64, i8, i8x64)
+        );
+    }
+
+    #[test]
+    fn sort() {
+        test_sort!(
+            (f64x8, f64),
+            (f32x16, f32),
+            (u64x8, u64),
+            (i64x8, i64),
+            (u32x16, u32),
+            (i32x16, i32),
+            (u16x32, u16),
+            (i16x32, i16),
+            (u8x64, u8),
+            (i8x64, i8)
+        );
+        test_sort!(nan: (f64x8, f64), (f32x16, f32));
+    }
+
+    #[test]
+    fn array_traits() {
+        test_array_traits!(
+            (f64x8, f64, 8),
+            (f32x16, f32, 16),
+            (u64x8, u64, 8),
+            (i64x8, i64, 8),
+            (u32x16, u32, 16),
+            (i32x16, i32, 16),
+            (u16x32, u16, 32),
+            (i16x32, i16, 32),
+            (u8x64, u8, 64),
+            (i8x64, i8, 64),
+            (u128x4, u128, 4),
+            (i128x4, i128, 4)
+        );
+        test_from_iter_len!(
+            (f64x8, f64, 8),
+            (f32x16, f32, 16),
+            (u64x8, u64, 8),
+            (i64x8, i64, 8),
+            (u32x16, u32, 16),
+            (i32x16, i32, 16),
+            (u16x32, u16, 32),
+            (i16x32, i16, 32),
+            (u8x64, u8, 64),
+            (i8x64, i8, 64),
+            (u128x4, u128, 4),
+            (i128x4, i128, 4)
+        );
+        test_hash!(
+            u64x8, i64x8, u32x16, i32x16, u16x32, i16x32, u8x64, i8x64, u128x4,
+            i128x4
+        );
+    }
+
+    #[test]
+    fn fmt() {
+        test_float_fmt!((f64x8, f64), (f32x16, f32));
+        test_integer_fmt!(
+            (u64x8, u64),
+            (i64x8, i64),
+            (u32x16, u32),
+            (i32x16, i32),
+            (u16x32, u16),
+            (i16x32, i16),
+            (u8x64, u8),
+            (i8x64, i8)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        test_serde!(
+            (f64x8, f64),
+            (f32x16, f32),
+            (u64x8, u64),
+            (i64x8, i64),
+            (u32x16, u32),
+            (i32x16, i32),
+            (u16x32, u16),
+            (i16x32, i16),
+            (u8x64, u8),
+            (i8x64, i8),
+            (u128x4, u128),
+            (i128x4, i128)
+        );
+    }
 }
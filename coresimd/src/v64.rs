@@ -29,6 +29,15 @@ define_impl! { u8x8, u8, 8, i8x8, x0, x1, x2, x3, x4, x5, x6, x7 }
 define_ty! { i8x8, i8, i8, i8, i8, i8, i8, i8, i8 }
 define_impl! { i8x8, i8, 8, i8x8, x0, x1, x2, x3, x4, x5, x6, x7 }
 
+define_ty_doc! {
+    f16x4, u16, u16, u16, u16 |
+    /// A 64-bit vector with 4 half-precision (IEEE 754 binary16) lanes,
+    /// stored as their bit patterns.
+    ///
+    /// `PartialEq` compares the bit patterns; convert the lanes with
+    /// `to_f32` to compare their values.
+}
+
 // On `x86` corresponds to llvm's `x86_mmx` type.
 define_ty_doc! {
     __m64, i64 |
@@ -43,6 +52,41 @@ define_from!(u8x8, u32x2, i32x2, u16x4, i16x4, i8x8, __m64);
 define_from!(i8x8, u32x2, i32x2, u16x4, i16x4, u8x8, __m64);
 define_from!(__m64, i8x8, u32x2, i32x2, u16x4, i16x4, u8x8);
 
+define_array_traits!(
+    (f32x2, f32, 2),
+    (u32x2, u32, 2),
+    (i32x2, i32, 2),
+    (u16x4, u16, 4),
+    (i16x4, i16, 4),
+    (u8x8, u8, 8),
+    (i8x8, i8, 8)
+);
+define_integer_traits!(
+    u32x2,
+    i32x2,
+    u16x4,
+    i16x4,
+    u8x8,
+    i8x8
+);
+define_serde!(
+    (f32x2, f32, 2),
+    (u32x2, u32, 2),
+    (i32x2, i32, 2),
+    (u16x4, u16, 4),
+    (i16x4, i16, 4),
+    (u8x8, u8, 8),
+    (i8x8, i8, 8)
+);
+define_float_fmt!(f32x2);
+define_integer_fmt!(
+    u32x2,
+    i32x2,
+    u16x4,
+    i16x4,
+    u8x8,
+    i8x8
+);
 define_common_ops!(f32x2, u32x2, i32x2, u16x4, i16x4, u8x8, i8x8);
 define_float_ops!(f32x2);
 define_integer_ops!(
@@ -54,6 +98,100 @@ define_integer_ops!(
     (i8x8, i8)
 );
 define_signed_integer_ops!(i32x2, i16x4, i8x8);
+define_abs_diff!(
+    (u32x2, u32x2),
+    (i32x2, u32x2),
+    (u16x4, u16x4),
+    (i16x4, u16x4),
+    (u8x8, u8x8),
+    (i8x8, u8x8)
+);
+define_avg!(
+    (u32x2),
+    (u16x4, arm("neon"): vrhadd_u16, vhadd_u16),
+    (u8x8, arm("neon"): vrhadd_u8, vhadd_u8)
+);
+define_mul_q15_round!((i16x4, arm("neon"): vqrdmulh_s16));
+define_scan!(
+    simd_shuffle2, 2,
+    [0, 1],
+    [1]:
+    (f32x2, f32),
+    (u32x2, u32),
+    (i32x2, i32)
+);
+define_scan!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [1, 2]:
+    (u16x4, u16),
+    (i16x4, i16)
+);
+define_scan!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [1, 2, 4]:
+    (u8x8, u8),
+    (i8x8, i8)
+);
+define_compress!(
+    portable:
+    (f32x2, f32, i32x2),
+    (u32x2, u32, i32x2),
+    (i32x2, i32, i32x2),
+    (u16x4, u16, i16x4),
+    (i16x4, i16, i16x4),
+    (u8x8, u8, i8x8),
+    (i8x8, i8, i8x8)
+);
+define_min_max!(
+    f32x2,
+    u32x2,
+    i32x2,
+    u16x4,
+    i16x4,
+    u8x8,
+    i8x8
+);
+define_sort!(
+    simd_shuffle2, 2,
+    [0, 1],
+    [(2, 1)],
+    [(2, 1)]:
+    (f32x2, f32, ::core::f32::INFINITY),
+    (u32x2, u32, ::core::u32::MAX),
+    (i32x2, i32, ::core::i32::MAX)
+);
+define_sort!(
+    simd_shuffle4, 4,
+    [0, 1, 2, 3],
+    [(2, 1), (4, 2), (4, 1)],
+    [(4, 2), (4, 1)]:
+    (u16x4, u16, ::core::u16::MAX),
+    (i16x4, i16, ::core::i16::MAX)
+);
+define_sort!(
+    simd_shuffle8, 8,
+    [0, 1, 2, 3, 4, 5, 6, 7],
+    [(2, 1), (4, 2), (4, 1), (8, 4), (8, 2), (8, 1)],
+    [(8, 4), (8, 2), (8, 1)]:
+    (u8x8, u8, ::core::u8::MAX),
+    (i8x8, i8, ::core::i8::MAX)
+);
+define_f16!((f16x4, u16x4, f32x4, widen_x4, narrow_x4));
+define_serde_via!(
+    (f16x4, u16x4, to_bits, from_bits)
+);
+define_byte_order!(
+    (u16x4, u8x8, "llvm.bswap.v4i16",
+     arm("neon"): vrev16_u8),
+    (i16x4, u8x8, "llvm.bswap.v4i16",
+     arm("neon"): vrev16_u8),
+    (u32x2, u8x8, "llvm.bswap.v2i32",
+     arm("neon"): vrev32_u8),
+    (i32x2, u8x8, "llvm.bswap.v2i32",
+     arm("neon"): vrev32_u8)
+);
 define_casts!(
     (f32x2, f64x2, as_f64x2),
     (f32x2, u32x2, as_u32x2),
@@ -81,10 +219,144 @@ define_casts!(
 mod tests {
     use super::*;
 
+    #[test]
+    fn byte_order() {
+        test_byte_order!(
+            (u16x4, u16, u8x8),
+            (i16x4, i16, u8x8),
+            (u32x2, u32, u8x8),
+            (i32x2, i32, u8x8)
+        );
+    }
+
     #[test]
     fn operators() {
         test_ops_si!(i8x8, i16x4, i32x2);
         test_ops_ui!(u8x8, u16x4, u32x2);
         test_ops_f!(f32x2);
     }
+
+    #[test]
+    fn f16() {
+        test_f16!((f16x4, u16x4, f32x4));
+    }
+
+    #[test]
+    fn abs_diff() {
+        test_abs_diff!(
+            (i8x8, i8, u8),
+            (u8x8, u8, u8),
+            (i16x4, i16, u16),
+            (u16x4, u16, u16),
+            (i32x2, i32, u32),
+            (u32x2, u32, u32)
+        );
+    }
+
+    #[test]
+    fn avg() {
+        test_avg!(
+            (u8x8, u8),
+            (u16x4, u16),
+            (u32x2, u32)
+        );
+    }
+
+    #[test]
+    fn mul_q15_round() {
+        test_mul_q15_round!(i16x4);
+    }
+
+    #[test]
+    fn scan() {
+        test_scan!(
+            (f32x2, f32),
+            (u32x2, u32),
+            (i32x2, i32),
+            (u16x4, u16),
+            (i16x4, i16),
+            (u8x8, u8),
+            (i8x8, i8)
+        );
+    }
+
+    #[test]
+    fn compress() {
+        test_compress!(
+            (f32x2, f32, i32x2),
+            (u32x2, u32, i32x2),
+            (i32x2, i32, i32x2),
+            (u16x4, u16, i16x4),
+            (i16x4, i16, i16x4),
+            (u8x8, u8, i8x8),
+            (i8x8, i8, i8x8)
+        );
+    }
+
+    #[test]
+    fn sort() {
+        test_sort!(
+            (f32x2, f32),
+            (u32x2, u32),
+            (i32x2, i32),
+            (u16x4, u16),
+            (i16x4, i16),
+            (u8x8, u8),
+            (i8x8, i8)
+        );
+        test_sort!(nan: (f32x2, f32));
+    }
+
+    #[test]
+    fn array_traits() {
+        test_array_traits!(
+            (f32x2, f32, 2),
+            (u32x2, u32, 2),
+            (i32x2, i32, 2),
+            (u16x4, u16, 4),
+            (i16x4, i16, 4),
+            (u8x8, u8, 8),
+            (i8x8, i8, 8)
+        );
+        test_from_iter_len!(
+            (f32x2, f32, 2),
+            (u32x2, u32, 2),
+            (i32x2, i32, 2),
+            (u16x4, u16, 4),
+            (i16x4, i16, 4),
+            (u8x8, u8, 8),
+            (i8x8, i8, 8)
+        );
+        test_hash!(u32x2, i32x2, u16x4, i16x4, u8x8, i8x8);
+    }
+
+    #[test]
+    fn fmt() {
+        test_float_fmt!((f32x2, f32));
+        test_integer_fmt!(
+            (u32x2, u32),
+            (i32x2, i32),
+            (u16x4, u16),
+            (i16x4, i16),
+            (u8x8, u8),
+            (i8x8, i8)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        test_serde!(
+            (f32x2, f32),
+            (u32x2, u32),
+            (i32x2, i32),
+            (u16x4, u16),
+            (i16x4, i16),
+            (u8x8, u8),
+            (i8x8, i8)
+        );
+        test_serde_via!(
+            (f16x4, u16x4, u16, to_bits, from_bits)
+        );
+    }
 }
@@ -0,0 +1,187 @@
+//! Pointer vector types
+//!
+//! The lanes are stored as addresses in an integer vector of pointer width,
+//! which is what `addr` returns. Pointers to the primitive lane types can
+//! `gather` into and `scatter` from the vector of the same number of lanes.
+
+use simd_llvm::*;
+
+#[cfg(target_pointer_width = "32")]
+mod width {
+    pub use v64::{i32x2 as isizex2, u32x2 as usizex2};
+    pub use v128::{i32x4 as isizex4, u32x4 as usizex4};
+    pub use v256::{i32x8 as isizex8, u32x8 as usizex8};
+}
+
+#[cfg(target_pointer_width = "64")]
+mod width {
+    pub use v128::{i64x2 as isizex2, u64x2 as usizex2};
+    pub use v256::{i64x4 as isizex4, u64x4 as usizex4};
+    pub use v512::{i64x8 as isizex8, u64x8 as usizex8};
+}
+
+use self::width::*;
+
+#[cfg(target_pointer_width = "32")]
+#[allow(non_camel_case_types)]
+type usize_lane = u32;
+#[cfg(target_pointer_width = "64")]
+#[allow(non_camel_case_types)]
+type usize_lane = u64;
+
+define_ptr! {
+    cptrx2, const, 2, usizex2, isizex2, usize_lane, 0 => x0, 1 => x1 |
+    /// A vector of 2 `*const T` lanes.
+}
+define_ptr! {
+    cptrx4, const, 4, usizex4, isizex4, usize_lane,
+    0 => x0, 1 => x1, 2 => x2, 3 => x3 |
+    /// A vector of 4 `*const T` lanes.
+}
+define_ptr! {
+    cptrx8, const, 8, usizex8, isizex8, usize_lane,
+    0 => x0, 1 => x1, 2 => x2, 3 => x3, 4 => x4, 5 => x5, 6 => x6, 7 => x7 |
+    /// A vector of 8 `*const T` lanes.
+}
+define_ptr! {
+    mutptrx2, mut, 2, usizex2, isizex2, usize_lane, 0 => x0, 1 => x1 |
+    /// A vector of 2 `*mut T` lanes.
+}
+define_ptr! {
+    mutptrx4, mut, 4, usizex4, isizex4, usize_lane,
+    0 => x0, 1 => x1, 2 => x2, 3 => x3 |
+    /// A vector of 4 `*mut T` lanes.
+}
+define_ptr! {
+    mutptrx8, mut, 8, usizex8, isizex8, usize_lane,
+    0 => x0, 1 => x1, 2 => x2, 3 => x3, 4 => x4, 5 => x5, 6 => x6, 7 => x7 |
+    /// A vector of 8 `*mut T` lanes.
+}
+
+define_mut_ptr!(
+    (mutptrx2, cptrx2),
+    (mutptrx4, cptrx4),
+    (mutptrx8, cptrx8)
+);
+
+/// LLVM vectors of pointers, the operands of `simd_gather` and
+/// `simd_scatter`.
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct craw2<T>(*const T, *const T);
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct mraw2<T>(*mut T, *mut T);
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct craw4<T>(*const T, *const T, *const T, *const T);
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct mraw4<T>(*mut T, *mut T, *mut T, *mut T);
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct craw8<T>(
+    *const T, *const T, *const T, *const T,
+    *const T, *const T, *const T, *const T,
+);
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct mraw8<T>(
+    *mut T, *mut T, *mut T, *mut T, *mut T, *mut T, *mut T, *mut T,
+);
+
+define_gather!(
+    cptrx2, mutptrx2, craw2, mraw2, isizex2:
+    (f64, f64x2),
+    (u64, u64x2),
+    (i64, i64x2),
+    (f32, f32x2),
+    (u32, u32x2),
+    (i32, i32x2)
+);
+define_gather!(
+    cptrx4, mutptrx4, craw4, mraw4, isizex4:
+    (f64, f64x4),
+    (u64, u64x4),
+    (i64, i64x4),
+    (f32, f32x4),
+    (u32, u32x4),
+    (i32, i32x4),
+    (u16, u16x4),
+    (i16, i16x4)
+);
+define_gather!(
+    cptrx8, mutptrx8, craw8, mraw8, isizex8:
+    (f64, f64x8),
+    (u64, u64x8),
+    (i64, i64x8),
+    (f32, f32x8),
+    (u32, u32x8),
+    (i32, i32x8),
+    (u16, u16x8),
+    (i16, i16x8),
+    (u8, u8x8),
+    (i8, i8x8)
+);
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+
+    use super::*;
+
+    #[test]
+    fn addr() {
+        let a = [0_u32; 4];
+        let base = a.as_ptr();
+        let p = cptrx4::splat(base).wrapping_add(usizex4::new(0, 1, 2, 3));
+        let b = base as usize_lane;
+        assert_eq!(p.addr(), usizex4::new(b, b + 4, b + 8, b + 12));
+        assert_eq!(p.extract(2), &a[2] as *const u32);
+        let c = &a[3] as *const u32 as *const u8;
+        assert_eq!(p.cast::<u8>().extract(3), c);
+        assert_eq!(p.replace(1, ptr::null()).is_null(),
+                   isizex4::new(0, -1, 0, 0));
+        assert_eq!(cptrx4::from_addr(p.addr()), p);
+    }
+
+    #[test]
+    fn offset() {
+        let a: [i32; 8] = [0, 1, 2, 3, 4, 5, 6, 7];
+        let p = cptrx2::splat(&a[4] as *const i32);
+        let q = unsafe { p.offset(isizex2::new(-3, 2)) };
+        assert_eq!(q, cptrx2::new(&a[1], &a[6]));
+        assert_eq!(p.wrapping_offset(isizex2::new(-4, 3)).addr(),
+                   cptrx2::new(&a[0], &a[7]).addr());
+        assert_eq!(unsafe { q.gather() }, ::simd::i32x2::new(1, 6));
+    }
+
+    #[test]
+    fn gather_scatter() {
+        let mut a: [f64; 8] = [0., 1., 2., 3., 4., 5., 6., 7.];
+        let p = mutptrx8::splat(a.as_mut_ptr())
+            .wrapping_add(usizex8::new(7, 6, 5, 4, 3, 2, 1, 1));
+        let v = unsafe { p.as_const().gather() };
+        assert_eq!(v, ::simd::f64x8::new(7., 6., 5., 4., 3., 2., 1., 1.));
+
+        let w = ::simd::f64x8::new(10., 11., 12., 13., 14., 15., 16., 17.);
+        unsafe { p.scatter(w) };
+        // Lanes 6 and 7 both point to `a[1]`: the last one wins.
+        assert_eq!(a, [0., 17., 15., 14., 13., 12., 11., 10.]);
+
+        let c: cptrx8<f64> = p.into();
+        assert_eq!(c.addr(), p.addr());
+
+        let b: [u8; 4] = [1, 2, 3, 4];
+        let q = cptrx8::splat(b.as_ptr())
+            .wrapping_add(usizex8::new(3, 2, 1, 0, 0, 1, 2, 3));
+        let v = unsafe { q.gather() };
+        assert_eq!(v, ::simd::u8x8::new(4, 3, 2, 1, 1, 2, 3, 4));
+    }
+}
@@ -0,0 +1,138 @@
+//! Half-precision floating-point conversions (F16C)
+//!
+//! The F16C instructions convert between packed IEEE 754 binary16 values
+//! (stored in 16-bit integer lanes) and packed single-precision values.
+//!
+//! The reference is [Intel 64 and IA-32 Architectures Software Developer's
+//! Manual Volume 2: Instruction Set Reference, A-Z][intel64_ref].
+//!
+//! [intel64_ref]: http://www.intel.de/content/dam/www/public/us/en/documents/manuals/64-ia-32-architectures-software-developer-instruction-set-reference-manual-325383.pdf
+
+#[cfg(test)]
+use stdsimd_test::assert_instr;
+
+use v128::{f32x4, i16x8};
+use v256::f32x8;
+
+#[allow(improper_ctypes)]
+extern "C" {
+    #[link_name = "llvm.x86.vcvtph2ps.128"]
+    fn vcvtph2ps(a: i16x8) -> f32x4;
+    #[link_name = "llvm.x86.vcvtph2ps.256"]
+    fn vcvtph2ps256(a: i16x8) -> f32x8;
+    #[link_name = "llvm.x86.vcvtps2ph.128"]
+    fn vcvtps2ph(a: f32x4, imm8: i32) -> i16x8;
+    #[link_name = "llvm.x86.vcvtps2ph.256"]
+    fn vcvtps2ph256(a: f32x8, imm8: i32) -> i16x8;
+}
+
+/// Convert the 4 half-precision (16-bit) floating-point values in the
+/// lower 64 bits of `a` to packed single-precision (32-bit) floating-point
+/// values.
+#[inline(always)]
+#[target_feature = "+f16c"]
+#[cfg_attr(test, assert_instr(vcvtph2ps))]
+pub unsafe fn _mm_cvtph_ps(a: i16x8) -> f32x4 {
+    vcvtph2ps(a)
+}
+
+/// Convert the 8 half-precision (16-bit) floating-point values in `a` to
+/// packed single-precision (32-bit) floating-point values.
+#[inline(always)]
+#[target_feature = "+f16c"]
+#[cfg_attr(test, assert_instr(vcvtph2ps))]
+pub unsafe fn _mm256_cvtph_ps(a: i16x8) -> f32x8 {
+    vcvtph2ps256(a)
+}
+
+/// Convert the 4 packed single-precision (32-bit) floating-point values in
+/// `a` to half-precision (16-bit) values, stored in the lower 64 bits of
+/// the result. The upper 64 bits are zeroed.
+///
+/// Rounding is done according to `imm8`:
+///
+/// - `_MM_FROUND_TO_NEAREST_INT`: round to nearest, ties to even.
+/// - `_MM_FROUND_TO_NEG_INF`: round down, toward negative infinity.
+/// - `_MM_FROUND_TO_POS_INF`: round up, toward positive infinity.
+/// - `_MM_FROUND_TO_ZERO`: truncate.
+/// - `_MM_FROUND_CUR_DIRECTION`: use `MXCSR.RC`.
+#[inline(always)]
+#[target_feature = "+f16c"]
+#[cfg_attr(test, assert_instr(vcvtps2ph, imm8 = 0))]
+pub unsafe fn _mm_cvtps_ph(a: f32x4, imm8: i32) -> i16x8 {
+    macro_rules! call {
+        ($imm8:expr) => { vcvtps2ph(a, $imm8) }
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Convert the 8 packed single-precision (32-bit) floating-point values in
+/// `a` to half-precision (16-bit) values.
+///
+/// Rounding is done according to `imm8`, see [`_mm_cvtps_ph`].
+///
+/// [`_mm_cvtps_ph`]: fn._mm_cvtps_ph.html
+#[inline(always)]
+#[target_feature = "+f16c"]
+#[cfg_attr(test, assert_instr(vcvtps2ph, imm8 = 0))]
+pub unsafe fn _mm256_cvtps_ph(a: f32x8, imm8: i32) -> i16x8 {
+    macro_rules! call {
+        ($imm8:expr) => { vcvtps2ph256(a, $imm8) }
+    }
+    constify_imm8!(imm8, call)
+}
+
+#[cfg(test)]
+mod tests {
+    use stdsimd_test::simd_test;
+
+    use v128::{f32x4, i16x8};
+    use v256::f32x8;
+    use x86::i586::f16c;
+
+    #[simd_test = "f16c"]
+    unsafe fn _mm_cvtph_ps() {
+        // 1.0, -2.0, 0.5, 65504.0 (largest finite half)
+        let a = i16x8::new(0x3c00, -0x4000, 0x3800, 0x7bff, 0, 0, 0, 0);
+        let r = f16c::_mm_cvtph_ps(a);
+        assert_eq!(r, f32x4::new(1., -2., 0.5, 65504.));
+    }
+
+    #[simd_test = "f16c"]
+    unsafe fn _mm256_cvtph_ps() {
+        let a = i16x8::new(
+            0x3c00, -0x4000, 0x3800, 0x7bff, 0x0001, 0x7c00, 0x0000, -0x8000,
+        );
+        let r = f16c::_mm256_cvtph_ps(a);
+        let e = f32x8::new(
+            1., -2., 0.5, 65504., 5.960464477539063e-8,
+            ::std::f32::INFINITY, 0., -0.,
+        );
+        assert_eq!(r, e);
+    }
+
+    #[simd_test = "f16c"]
+    unsafe fn _mm_cvtps_ph() {
+        // 1 + 2^-11 is halfway between 1.0 and the next half.
+        let a = f32x4::new(1., -2., 1.00048828125, 1e6);
+        let r = f16c::_mm_cvtps_ph(a, 0);
+        let e = i16x8::new(0x3c00, -0x4000, 0x3c00, 0x7c00, 0, 0, 0, 0);
+        assert_eq!(r, e);
+        let r = f16c::_mm_cvtps_ph(a, 2);
+        let e = i16x8::new(0x3c00, -0x4000, 0x3c01, 0x7c00, 0, 0, 0, 0);
+        assert_eq!(r, e);
+        let r = f16c::_mm_cvtps_ph(a, 3);
+        let e = i16x8::new(0x3c00, -0x4000, 0x3c00, 0x7bff, 0, 0, 0, 0);
+        assert_eq!(r, e);
+    }
+
+    #[simd_test = "f16c"]
+    unsafe fn _mm256_cvtps_ph() {
+        let a = f32x8::new(1., -2., 0.5, 65504., 0., -0., 1e-10, -1e6);
+        let r = f16c::_mm256_cvtps_ph(a, 0);
+        let e = i16x8::new(
+            0x3c00, -0x4000, 0x3800, 0x7bff, 0, -0x8000, 0, -0x0400,
+        );
+        assert_eq!(r, e);
+    }
+}
@@ -11,6 +11,7 @@ pub use self::sse41::*;
 pub use self::sse42::*;
 pub use self::avx::*;
 pub use self::avx2::*;
+pub use self::f16c::*;
 
 pub use self::abm::*;
 pub use self::bmi::*;
@@ -30,6 +31,7 @@ mod sse41;
 mod sse42;
 mod avx;
 mod avx2;
+mod f16c;
 
 mod abm;
 mod bmi;
@@ -123,12 +123,14 @@
 //! [simd_soundness_bug]: https://github.com/rust-lang/rust/issues/44367
 //! [target_feature_impr]: https://github.com/rust-lang/rust/issues/44839
 
-#![feature(macro_reexport, const_fn, const_atomic_usize_new)]
+#![feature(macro_reexport, const_fn, const_atomic_usize_new, used)]
 
-/// We re-export run-time feature detection for those architectures that have
-/// suport for it in `core`:
+/// We re-export the feature names of run-time feature detection for those
+/// architectures that have suport for it in `core`. The detection itself is
+/// wrapped by `runtime` to register `STDSIMD_DISABLE`:
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-#[macro_reexport(cfg_feature_enabled, __unstable_detect_feature)]
+#[macro_use(__unstable_feature)]
+#[macro_reexport(__unstable_feature)]
 extern crate coresimd;
 
 #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
@@ -138,10 +140,23 @@ extern crate coresimd;
 pub mod vendor {
     pub use coresimd::vendor::*;
 
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub use super::runtime::{detected_features, is_detected, microarch_level,
+                             __unstable_detect_all, __unstable_detect_any,
+                             __unstable_detect_feature,
+                             __unstable_detect_level};
+
     #[cfg(all(target_os = "linux",
               any(target_arch = "arm", target_arch = "aarch64",
                   target_arch = "powerpc64")))]
-    pub use super::runtime::{__unstable_detect_feature, __Feature};
+    pub use super::runtime::{__unstable_detect_all, __unstable_detect_any,
+                             __unstable_detect_feature, __Feature};
+    #[cfg(all(target_os = "linux",
+              any(target_arch = "arm", target_arch = "aarch64",
+                  target_arch = "powerpc64")))]
+    pub use super::runtime::{detected_features, is_detected,
+                             override_features, DetectedFeatures, Feature,
+                             ParseFeatureError};
 }
 
 /// Platform independent SIMD vector types and operations.
@@ -149,8 +164,9 @@ pub mod simd {
     pub use coresimd::simd::*;
 }
 
-#[cfg(all(target_os = "linux",
-          any(target_arch = "arm", target_arch = "aarch64",
-              target_arch = "powerpc64")))]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64",
+          all(target_os = "linux",
+              any(target_arch = "arm", target_arch = "aarch64",
+                  target_arch = "powerpc64"))))]
 #[macro_use]
 mod runtime;
@@ -1,22 +1,30 @@
 //! Run-time feature detection on ARM Aarch64.
-use super::{bit, linux};
+use super::{cache, linux};
 
 #[macro_export]
 #[doc(hidden)]
-macro_rules! __unstable_detect_feature {
+macro_rules! __unstable_feature {
     ("neon") => {
         // FIXME: this should be removed once we rename Aarch64 neon to asimd
-        $crate::vendor::__unstable_detect_feature($crate::vendor::__Feature::asimd{})
+        $crate::vendor::__Feature::asimd{}
     };
     ("asimd") => {
-        $crate::vendor::__unstable_detect_feature($crate::vendor::__Feature::asimd{})
+        $crate::vendor::__Feature::asimd{}
     };
     ("pmull") => {
-        $crate::vendor::__unstable_detect_feature($crate::vendor::__Feature::pmull{})
+        $crate::vendor::__Feature::pmull{}
     };
     ($t:tt) => { compile_error!(concat!("unknown arm target feature: ", $t)) };
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __unstable_detect_feature {
+    ($t:tt) => {
+        $crate::vendor::__unstable_detect_feature(__unstable_feature!($t))
+    };
+}
+
 /// ARM Aarch64 CPU Feature enum. Each variant denotes a position in a bitset
 /// for a particular feature.
 ///
@@ -31,12 +39,21 @@ pub enum __Feature {
     pmull,
 }
 
-pub fn detect_features<T: linux::FeatureQuery>(mut x: T) -> usize {
-    let mut value: usize = 0;
+/// The `cfg_feature_enabled!` names of the features, in `__Feature` order.
+pub const FEATURE_NAMES: &[&str] = &["asimd", "pmull"];
+
+/// Names accepted by `cfg_feature_enabled!` for another feature.
+// FIXME: this should be removed once we rename Aarch64 neon to asimd
+pub const FEATURE_ALIASES: &[(&str, &str)] = &[("neon", "asimd")];
+
+pub fn detect_features<T: linux::FeatureQuery>(
+    mut x: T,
+) -> cache::Initializer {
+    let mut value = cache::Initializer::default();
     {
         let mut enable_feature = |f| {
             if x.has_feature(&f) {
-                value = bit::set(value, f as u32);
+                value.set(f as u32);
             }
         };
         enable_feature(__Feature::asimd);
@@ -1,31 +1,372 @@
 //! Cache of run-time feature detection
 
+use std::env;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::usize;
 
-use super::bit;
+/// Number of feature bits stored in each word of the cache. Only the lower
+/// 32 bits of each `AtomicUsize` are used so that the layout is the same on
+/// 32-bit and 64-bit targets.
+const WORD_BITS: u32 = 32;
+
+/// Number of words of the cache.
+const WORDS: usize = 4;
+
+/// Maximum number of features the cache can hold.
+pub const CAPACITY: u32 = WORD_BITS * WORDS as u32;
+
+/// The set of features detected by `detect_features`, used to initialize
+/// the cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Initializer([u32; WORDS]);
+
+impl Initializer {
+    /// Marks the feature at position `bit` as detected.
+    ///
+    /// # Panics
+    ///
+    /// If `bit >= CAPACITY`.
+    #[inline]
+    pub fn set(&mut self, bit: u32) {
+        assert!(bit < CAPACITY, "feature bit out of range");
+        self.0[(bit / WORD_BITS) as usize] |= 1 << (bit % WORD_BITS);
+    }
+
+    /// Removes the features of `other` from the set.
+    #[inline]
+    pub fn remove(&mut self, other: &Initializer) {
+        for (w, o) in self.0.iter_mut().zip(other.0.iter()) {
+            *w &= !o;
+        }
+    }
+
+    /// Is the feature at position `bit` detected?
+    #[inline]
+    pub fn test(&self, bit: u32) -> bool {
+        bit < CAPACITY
+            && self.0[(bit / WORD_BITS) as usize] & (1 << (bit % WORD_BITS))
+                != 0
+    }
+}
+
+/// The cache has not been filled yet.
+const UNINIT: usize = 0;
+/// The cache is being filled, or `disabled` is being updated.
+const BUSY: usize = 1;
+/// The cache has been filled and no longer changes.
+const READY: usize = 2;
+
+/// A multi-word bitset of features with a separate state word.
+///
+/// Every bit pattern is a valid set of features: whether the cache has
+/// been filled is tracked by `state` alone. The `disabled` features are
+/// removed from the detected ones when the cache is filled. Filling the
+/// cache and adding to `disabled` both hold the `BUSY` state, so a mask
+/// is either applied by the fill or rejected.
+pub struct Cache {
+    state: AtomicUsize,
+    words: [AtomicUsize; WORDS],
+    disabled: [AtomicUsize; WORDS],
+}
+
+impl Cache {
+    /// Creates an uninitialized cache.
+    pub const fn new() -> Cache {
+        Cache {
+            state: AtomicUsize::new(UNINIT),
+            words: [
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+            ],
+            disabled: [
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+            ],
+        }
+    }
+
+    /// Masks the features of `mask` out of the cache.
+    ///
+    /// Returns `false`, without masking anything, if the cache has already
+    /// been initialized.
+    pub fn disable(&self, mask: &Initializer) -> bool {
+        if !self.lock() {
+            return false;
+        }
+        for (d, &m) in self.disabled.iter().zip(mask.0.iter()) {
+            d.fetch_or(m as usize, Ordering::Relaxed);
+        }
+        self.state.store(UNINIT, Ordering::Release);
+        true
+    }
+
+    /// Test the `bit` of the storage. If the storage has not been
+    /// initialized, initializes it with the result of `f()`.
+    ///
+    /// Threads racing on the first call may all run `f`, but only one of
+    /// them stores its result.
+    #[inline]
+    pub fn test<F>(&self, bit: u32, f: F) -> bool
+    where
+        F: FnOnce() -> Initializer,
+    {
+        if self.state.load(Ordering::Acquire) != READY {
+            self.initialize(f);
+        }
+        if bit >= CAPACITY {
+            return false;
+        }
+        let word = self.words[(bit / WORD_BITS) as usize]
+            .load(Ordering::Relaxed);
+        word & (1 << (bit % WORD_BITS)) != 0
+    }
+
+    /// Tests whether all the features of `mask` are set, reading each word
+    /// of the storage at most once. If the storage has not been
+    /// initialized, initializes it with the result of `f()`.
+    #[inline]
+    pub fn test_all<F>(&self, mask: &Initializer, f: F) -> bool
+    where
+        F: FnOnce() -> Initializer,
+    {
+        if self.state.load(Ordering::Acquire) != READY {
+            self.initialize(f);
+        }
+        self.words.iter().zip(mask.0.iter()).all(|(w, &m)| {
+            m == 0 || w.load(Ordering::Relaxed) & m as usize == m as usize
+        })
+    }
+
+    /// Tests whether any feature of `mask` is set, reading each word of
+    /// the storage at most once. If the storage has not been initialized,
+    /// initializes it with the result of `f()`.
+    #[inline]
+    pub fn test_any<F>(&self, mask: &Initializer, f: F) -> bool
+    where
+        F: FnOnce() -> Initializer,
+    {
+        if self.state.load(Ordering::Acquire) != READY {
+            self.initialize(f);
+        }
+        self.words.iter().zip(mask.0.iter()).any(|(w, &m)| {
+            m != 0 && w.load(Ordering::Relaxed) & m as usize != 0
+        })
+    }
+
+    /// Moves the cache from `UNINIT` to `BUSY`, waiting for other threads
+    /// holding `BUSY`. Returns `false` if the cache is `READY`.
+    fn lock(&self) -> bool {
+        loop {
+            match self.state.compare_exchange_weak(
+                UNINIT,
+                BUSY,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return true,
+                Err(READY) => return false,
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Fills the cache with the result of `f()` without the `disabled`
+    /// features, unless another thread did it first.
+    #[cold]
+    fn initialize<F>(&self, f: F)
+    where
+        F: FnOnce() -> Initializer,
+    {
+        let value = f();
+        if !self.lock() {
+            return;
+        }
+        let words = self.words.iter().zip(self.disabled.iter());
+        for ((w, d), &v) in words.zip(value.0.iter()) {
+            let v = v as usize & !d.load(Ordering::Relaxed);
+            w.store(v, Ordering::Relaxed);
+        }
+        self.state.store(READY, Ordering::Release);
+    }
+}
 
 /// This global variable is a bitset used to cache the features supported by
-/// the
-/// CPU.
-static CACHE: AtomicUsize = AtomicUsize::new(usize::MAX);
+/// the CPU.
+static CACHE: Cache = Cache::new();
 
-/// Test the `bit` of the storage. If the storage has not been initialized,
-/// initializes it with the result of `f()`.
+/// Test the `bit` of the global cache. If the cache has not been
+/// initialized, initializes it with the result of `f()`.
 ///
-/// On its first invocation, it detects the CPU features and caches them in the
-/// `FEATURES` global variable as an `AtomicUsize`.
+/// On its first invocation, it detects the CPU features and caches them in
+/// the `CACHE` global variable.
 ///
-/// It uses the `__Feature` variant to index into this variable as a bitset. If
-/// the bit is set, the feature is enabled, and otherwise it is disabled.
+/// It uses the `__Feature` variant to index into this variable as a bitset.
+/// If the bit is set, the feature is enabled, and otherwise it is disabled.
 ///
 /// PLEASE: do not use this, it is an implementation detail subject to change.
 pub fn test<F>(bit: u32, f: F) -> bool
 where
-    F: FnOnce() -> usize,
+    F: FnOnce() -> Initializer,
 {
-    if CACHE.load(Ordering::Relaxed) == usize::MAX {
-        CACHE.store(f(), Ordering::Relaxed);
+    CACHE.test(bit, || detect(f))
+}
+
+/// Tests whether all the features of `mask` are set in the global cache,
+/// see `Cache::test_all`.
+pub fn test_all<F>(mask: &Initializer, f: F) -> bool
+where
+    F: FnOnce() -> Initializer,
+{
+    CACHE.test_all(mask, || detect(f))
+}
+
+/// Tests whether any feature of `mask` is set in the global cache, see
+/// `Cache::test_any`.
+pub fn test_any<F>(mask: &Initializer, f: F) -> bool
+where
+    F: FnOnce() -> Initializer,
+{
+    CACHE.test_any(mask, || detect(f))
+}
+
+/// Detects the features with `f`, without those of `env_disabled`.
+fn detect<F>(f: F) -> Initializer
+where
+    F: FnOnce() -> Initializer,
+{
+    let mut value = f();
+    value.remove(&env_disabled());
+    value
+}
+
+/// Masks the features of `mask` out of the global cache, see
+/// `Cache::disable`.
+pub fn disable(mask: &Initializer) -> bool {
+    CACHE.disable(mask)
+}
+
+/// The features listed in the `STDSIMD_DISABLE` environment variable.
+fn env_disabled() -> Initializer {
+    env::var("STDSIMD_DISABLE")
+        .map(|list| super::feature::parse_disabled(&list))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn initializer() {
+        let mut v = Initializer::default();
+        for &bit in &[0, 31, 32, 63, 64, 65, 100, CAPACITY - 1] {
+            assert!(!v.test(bit));
+            v.set(bit);
+            assert!(v.test(bit));
+        }
+        assert!(!v.test(1));
+        assert!(!v.test(CAPACITY));
+    }
+
+    #[test]
+    #[should_panic]
+    fn initializer_out_of_range() {
+        Initializer::default().set(CAPACITY);
+    }
+
+    #[test]
+    fn more_than_64_features() {
+        let bits = [0, 5, 31, 32, 63, 64, 70, 96, 127];
+        let calls = Cell::new(0);
+        let detect = || {
+            calls.set(calls.get() + 1);
+            let mut v = Initializer::default();
+            for &b in &bits {
+                v.set(b);
+            }
+            v
+        };
+        let cache = Cache::new();
+        for bit in 0..CAPACITY + 8 {
+            assert_eq!(cache.test(bit, &detect), bits.contains(&bit));
+        }
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn disable() {
+        let detect = || {
+            let mut v = Initializer::default();
+            for b in 0..70 {
+                v.set(b);
+            }
+            v
+        };
+        let mut mask = Initializer::default();
+        mask.set(3);
+        mask.set(66);
+        let cache = Cache::new();
+        assert!(cache.disable(&mask));
+        for bit in 0..70 {
+            assert_eq!(cache.test(bit, &detect), bit != 3 && bit != 66);
+        }
+        // Once initialized, the cache can no longer be changed.
+        let mut late = Initializer::default();
+        late.set(4);
+        assert!(!cache.disable(&late));
+        assert!(cache.test(4, &detect));
+    }
+
+    #[test]
+    fn test_all_any() {
+        let detect = || {
+            let mut v = Initializer::default();
+            for &b in &[1, 40, 100] {
+                v.set(b);
+            }
+            v
+        };
+        let mask = |bits: &[u32]| {
+            let mut m = Initializer::default();
+            for &b in bits {
+                m.set(b);
+            }
+            m
+        };
+        let cache = Cache::new();
+        assert!(cache.test_all(&mask(&[1, 40, 100]), &detect));
+        assert!(cache.test_all(&mask(&[40]), &detect));
+        assert!(cache.test_all(&mask(&[]), &detect));
+        assert!(!cache.test_all(&mask(&[1, 41]), &detect));
+        assert!(!cache.test_all(&mask(&[1, 40, 101]), &detect));
+        assert!(cache.test_any(&mask(&[0, 100]), &detect));
+        assert!(cache.test_any(&mask(&[2, 3, 40]), &detect));
+        assert!(!cache.test_any(&mask(&[0, 41, 127]), &detect));
+        assert!(!cache.test_any(&mask(&[]), &detect));
+    }
+
+    #[test]
+    fn all_bits_set() {
+        // A set with every feature is not mistaken for an uninitialized
+        // cache.
+        let calls = Cell::new(0);
+        let detect = || {
+            calls.set(calls.get() + 1);
+            let mut v = Initializer::default();
+            for b in 0..CAPACITY {
+                v.set(b);
+            }
+            v
+        };
+        let cache = Cache::new();
+        for bit in 0..CAPACITY {
+            assert!(cache.test(bit, &detect));
+        }
+        assert_eq!(calls.get(), 1);
     }
-    bit::test(CACHE.load(Ordering::Relaxed), bit)
 }
@@ -0,0 +1,182 @@
+//! Public run-time feature queries
+//!
+//! A `Feature` is a position in the feature cache. Its name is looked up in
+//! the `FEATURE_NAMES` table of the architecture, which lists the names
+//! accepted by `cfg_feature_enabled!` in `__Feature` order.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{cache, detect_features, FEATURE_ALIASES, FEATURE_NAMES};
+
+/// A CPU feature that can be detected at run-time.
+///
+/// Features are looked up by their `target_feature` name:
+///
+/// ```ignore
+/// let avx2: Feature = "avx2".parse().unwrap();
+/// if is_detected(avx2) {
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Feature(u8);
+
+impl Feature {
+    /// Returns the `target_feature` name of the feature.
+    pub fn name(self) -> &'static str {
+        FEATURE_NAMES[self.0 as usize]
+    }
+}
+
+impl FromStr for Feature {
+    type Err = ParseFeatureError;
+
+    fn from_str(name: &str) -> Result<Feature, ParseFeatureError> {
+        let name = FEATURE_ALIASES
+            .iter()
+            .find(|&&(alias, _)| alias == name)
+            .map_or(name, |&(_, target)| target);
+        FEATURE_NAMES
+            .iter()
+            .position(|&n| n == name)
+            .map(|i| Feature(i as u8))
+            .ok_or(ParseFeatureError(()))
+    }
+}
+
+impl fmt::Debug for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Feature").field(&self.name()).finish()
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The error returned when parsing an unknown feature name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseFeatureError(());
+
+impl fmt::Display for ParseFeatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown target feature")
+    }
+}
+
+/// Is `feature` supported by the host CPU?
+///
+/// Unlike `cfg_feature_enabled!`, this always queries the run-time cache,
+/// even for features enabled at compile-time.
+pub fn is_detected(feature: Feature) -> bool {
+    cache::test(u32::from(feature.0), detect_features)
+}
+
+/// Disables `features` in run-time detection.
+///
+/// `cfg_feature_enabled!` and `is_detected` report the disabled features
+/// as missing, which allows testing the fallbacks of run-time dispatch on
+/// a CPU that has the features. Features enabled at compile-time are
+/// still reported by `cfg_feature_enabled!`.
+///
+/// This must be called before the first feature query: it returns `false`,
+/// without disabling anything, once the features have been detected.
+pub fn override_features<I>(features: I) -> bool
+where
+    I: IntoIterator<Item = Feature>,
+{
+    let mut mask = cache::Initializer::default();
+    for f in features {
+        mask.set(u32::from(f.0));
+    }
+    cache::disable(&mask)
+}
+
+/// Parses a comma-separated list of feature names, like the value of the
+/// `STDSIMD_DISABLE` environment variable. Unknown names are ignored.
+pub fn parse_disabled(list: &str) -> cache::Initializer {
+    let mut mask = cache::Initializer::default();
+    for name in list.split(',') {
+        if let Ok(f) = name.trim().parse::<Feature>() {
+            mask.set(u32::from(f.0));
+        }
+    }
+    mask
+}
+
+/// Returns an iterator over the features supported by the host CPU.
+pub fn detected_features() -> DetectedFeatures {
+    DetectedFeatures { next: 0 }
+}
+
+/// Iterator over the features supported by the host CPU, see
+/// `detected_features`.
+#[derive(Clone, Debug)]
+pub struct DetectedFeatures {
+    next: u8,
+}
+
+impl Iterator for DetectedFeatures {
+    type Item = Feature;
+
+    fn next(&mut self) -> Option<Feature> {
+        while (self.next as usize) < FEATURE_NAMES.len() {
+            let f = Feature(self.next);
+            self.next += 1;
+            if is_detected(f) {
+                return Some(f);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        for (i, &name) in FEATURE_NAMES.iter().enumerate() {
+            let f: Feature = name.parse().unwrap();
+            assert_eq!(f, Feature(i as u8));
+            assert_eq!(f.name(), name);
+            assert_eq!(f.to_string(), name);
+        }
+        for &(alias, target) in FEATURE_ALIASES {
+            assert_eq!(alias.parse::<Feature>(), target.parse::<Feature>());
+        }
+        assert_eq!("".parse::<Feature>(), Err(ParseFeatureError(())));
+        assert!("avx3".parse::<Feature>().is_err());
+    }
+
+    #[test]
+    fn disabled_list() {
+        let bit = |name: &str| u32::from(name.parse::<Feature>().unwrap().0);
+        let (a, b) = (FEATURE_NAMES[0], FEATURE_NAMES[1]);
+        let list = format!("{}, unknown,{},", a, b);
+        let mask = parse_disabled(&list);
+        for i in 0..FEATURE_NAMES.len() as u32 {
+            assert_eq!(mask.test(i), i == bit(a) || i == bit(b));
+        }
+        assert_eq!(parse_disabled(""), cache::Initializer::default());
+    }
+
+    #[test]
+    fn detected() {
+        let mut last = None;
+        for f in detected_features() {
+            assert!(is_detected(f));
+            // Features are yielded once, in order.
+            assert!(Some(f) > last);
+            last = Some(f);
+        }
+        let n = FEATURE_NAMES.len();
+        let detected = detected_features().count();
+        let all = (0..n).filter(|&i| is_detected(Feature(i as u8))).count();
+        assert_eq!(detected, all);
+    }
+}
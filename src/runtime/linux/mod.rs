@@ -6,12 +6,13 @@ mod auxvec;
 pub use self::auxvec::*;
 
 use super::__Feature;
+use super::cache;
 
 pub trait FeatureQuery {
     fn has_feature(&mut self, x: &__Feature) -> bool;
 }
 
-fn detect_features_impl<T: FeatureQuery>(x: T) -> usize {
+fn detect_features_impl<T: FeatureQuery>(x: T) -> cache::Initializer {
     #[cfg(target_arch = "arm")]
     {
         super::arm::detect_features(x)
@@ -27,7 +28,7 @@ fn detect_features_impl<T: FeatureQuery>(x: T) -> usize {
 }
 
 /// Detects CPU features:
-pub fn detect_features() -> usize {
+pub fn detect_features() -> cache::Initializer {
     // Try to read the ELF Auxiliary Vector
     if let Ok(v) = auxvec::AuxVec::new() {
         return detect_features_impl(v);
@@ -37,5 +38,5 @@ pub fn detect_features() -> usize {
         return detect_features_impl(v);
     }
     // Otherwise all features are disabled
-    0
+    cache::Initializer::default()
 }
@@ -4,8 +4,87 @@
 ///
 /// This macro performs run-time feature detection. It returns true if the host
 /// CPU in which the binary is running on supports a particular feature.
+///
+/// Several features can be queried at once with `all(...)` and `any(...)`:
+///
+/// ```ignore
+/// if cfg_feature_enabled!(all("avx2", "fma")) {
+///     // ...
+/// }
+/// ```
+///
+/// The features enabled at compile-time are known to be supported; the
+/// others are looked up together in the run-time cache.
+///
+/// The x86-64 microarchitecture levels, `"x86-64-v1"` to `"x86-64-v4"`, can
+/// only be queried on their own:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate stdsimd;
+///
+/// fn main() {
+///     // error: microarchitecture level x86-64-v3 cannot be used in all(...)
+///     cfg_feature_enabled!(all("x86-64-v3", "avx512f"));
+/// }
+/// ```
+///
+/// This is also an error when another feature of `any(...)` is enabled at
+/// compile-time:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate stdsimd;
+///
+/// fn main() {
+///     // error: microarchitecture level x86-64-v3 cannot be used in any(...)
+///     cfg_feature_enabled!(any("sse2", "x86-64-v3"));
+/// }
+/// ```
 #[macro_export]
 macro_rules! cfg_feature_enabled {
+    (all($($name:tt),+ $(,)*)) => (
+        {
+            #[cfg(all($(target_feature = $name),+))]
+            {
+                // Check the names even if the result is known.
+                let _ = [$(__unstable_feature!($name) as u32),+];
+                true
+            }
+            #[cfg(not(all($(target_feature = $name),+)))]
+            {
+                $crate::vendor::__unstable_detect_all(&[$(
+                    {
+                        #[cfg(target_feature = $name)]
+                        {
+                            let _ = __unstable_feature!($name);
+                            None
+                        }
+                        #[cfg(not(target_feature = $name))]
+                        {
+                            Some(__unstable_feature!($name) as u32)
+                        }
+                    }
+                ),+])
+            }
+        }
+    );
+    (any($($name:tt),+ $(,)*)) => (
+        {
+            #[cfg(any($(target_feature = $name),+))]
+            {
+                // Check the names even if the result is known.
+                let _ = [$(__unstable_feature!($name) as u32),+];
+                true
+            }
+            #[cfg(not(any($(target_feature = $name),+)))]
+            {
+                $crate::vendor::__unstable_detect_any(&[$(
+                    __unstable_feature!($name) as u32
+                ),+])
+            }
+        }
+    );
     ($name:tt) => (
         {
             #[cfg(target_feature = $name)]
@@ -29,6 +108,15 @@ macro_rules! __unstable_detect_feature {
     ($t:tt) => { compile_error!(concat!("unknown target feature: ", $t)) };
 }
 
+/// In all unsupported architectures using the macro is an error
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64",
+              target_arch = "arm", target_arch = "aarch64")))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __unstable_feature {
+    ($t:tt) => { compile_error!(concat!("unknown target feature: ", $t)) };
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(target_arch = "x86_64")]
@@ -36,4 +124,24 @@ mod tests {
     fn test_macros() {
         assert!(cfg_feature_enabled!("sse"));
     }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_all_any() {
+        assert!(cfg_feature_enabled!(all("sse", "sse2")));
+        assert!(cfg_feature_enabled!(any("sse", "avx512f",)));
+
+        let avx2 = cfg_feature_enabled!("avx2");
+        let fma = cfg_feature_enabled!("fma");
+        let bmi2 = cfg_feature_enabled!("bmi2");
+        assert_eq!(
+            cfg_feature_enabled!(all("avx2", "fma", "bmi2")),
+            avx2 && fma && bmi2
+        );
+        assert_eq!(
+            cfg_feature_enabled!(any("avx2", "fma", "bmi2")),
+            avx2 || fma || bmi2
+        );
+        assert_eq!(cfg_feature_enabled!(all("sse2", "avx2")), avx2);
+    }
 }
@@ -1,27 +1,43 @@
 //! Run-time feature detection
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
 mod cache;
-mod bit;
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+mod feature;
 
 #[macro_use]
 mod macros;
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[macro_use]
+mod x86;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub use self::x86::{detected_features, is_detected, microarch_level,
+                    __unstable_detect_all, __unstable_detect_any,
+                    __unstable_detect_feature, __unstable_detect_level};
+
 #[cfg(all(target_arch = "arm", target_os = "linux"))]
 #[macro_use]
 mod arm;
 #[cfg(all(target_arch = "arm", target_os = "linux"))]
 pub use self::arm::__Feature;
+#[cfg(all(target_arch = "arm", target_os = "linux"))]
+use self::arm::{FEATURE_ALIASES, FEATURE_NAMES};
 
 #[cfg(all(target_arch = "aarch64", target_os = "linux"))]
 #[macro_use]
 mod aarch64;
 #[cfg(all(target_arch = "aarch64", target_os = "linux"))]
 pub use self::aarch64::__Feature;
+#[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+use self::aarch64::{FEATURE_ALIASES, FEATURE_NAMES};
 
 #[cfg(all(target_arch = "powerpc64", target_os = "linux"))]
 #[macro_use]
 mod powerpc64;
 #[cfg(all(target_arch = "powerpc64", target_os = "linux"))]
 pub use self::powerpc64::__Feature;
+#[cfg(all(target_arch = "powerpc64", target_os = "linux"))]
+use self::powerpc64::{FEATURE_ALIASES, FEATURE_NAMES};
 
 #[cfg(all(target_os = "linux",
           any(target_arch = "arm", target_arch = "aarch64",
@@ -33,8 +49,36 @@ mod linux;
               target_arch = "powerpc64")))]
 pub use self::linux::detect_features;
 
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub use self::feature::{detected_features, is_detected, override_features,
+                        DetectedFeatures, Feature, ParseFeatureError};
+
 /// Performs run-time feature detection.
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
 #[doc(hidden)]
 pub fn __unstable_detect_feature(x: __Feature) -> bool {
     cache::test(x as u32, detect_features)
 }
+
+/// Performs run-time detection of all the features of `bits`, skipping the
+/// `None` entries, which are enabled at compile-time.
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[doc(hidden)]
+pub fn __unstable_detect_all(bits: &[Option<u32>]) -> bool {
+    let mut mask = cache::Initializer::default();
+    for &bit in bits.iter().filter_map(|b| b.as_ref()) {
+        mask.set(bit);
+    }
+    cache::test_all(&mask, detect_features)
+}
+
+/// Performs run-time detection of any of the features of `bits`.
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[doc(hidden)]
+pub fn __unstable_detect_any(bits: &[u32]) -> bool {
+    let mut mask = cache::Initializer::default();
+    for &bit in bits {
+        mask.set(bit);
+    }
+    cache::test_any(&mask, detect_features)
+}
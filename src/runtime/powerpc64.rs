@@ -1,21 +1,29 @@
 //! Run-time feature detection on PowerPC64.
-use super::{bit, linux};
+use super::{cache, linux};
 
 #[macro_export]
 #[doc(hidden)]
-macro_rules! __unstable_detect_feature {
+macro_rules! __unstable_feature {
     ("altivec") => {
-        $crate::vendor::__unstable_detect_feature($crate::vendor::__Feature::altivec{})
+        $crate::vendor::__Feature::altivec{}
     };
     ("vsx") => {
-        $crate::vendor::__unstable_detect_feature($crate::vendor::__Feature::vsx{})
+        $crate::vendor::__Feature::vsx{}
     };
     ("power8") => {
-        $crate::vendor::__unstable_detect_feature($crate::vendor::__Feature::power8{})
+        $crate::vendor::__Feature::power8{}
     };
     ($t:tt) => { compile_error!(concat!("unknown PowerPC target feature: ", $t)) };
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __unstable_detect_feature {
+    ($t:tt) => {
+        $crate::vendor::__unstable_detect_feature(__unstable_feature!($t))
+    };
+}
+
 /// PowerPC CPU Feature enum. Each variant denotes a position in a bitset
 /// for a particular feature.
 ///
@@ -32,12 +40,20 @@ pub enum __Feature {
     power8,
 }
 
-pub fn detect_features<T: linux::FeatureQuery>(mut x: T) -> usize {
-    let mut value: usize = 0;
+/// The `cfg_feature_enabled!` names of the features, in `__Feature` order.
+pub const FEATURE_NAMES: &[&str] = &["altivec", "vsx", "power8"];
+
+/// Names accepted by `cfg_feature_enabled!` for another feature.
+pub const FEATURE_ALIASES: &[(&str, &str)] = &[];
+
+pub fn detect_features<T: linux::FeatureQuery>(
+    mut x: T,
+) -> cache::Initializer {
+    let mut value = cache::Initializer::default();
     {
         let mut enable_feature = |f| {
             if x.has_feature(&f) {
-                value = bit::set(value, f as u32);
+                value.set(f as u32);
             }
         };
         enable_feature(__Feature::altivec);
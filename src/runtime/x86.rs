@@ -0,0 +1,122 @@
+//! Run-time feature detection on x86.
+//!
+//! The detection itself is done by `coresimd`. This module registers the
+//! `STDSIMD_DISABLE` environment variable (e.g. `STDSIMD_DISABLE=avx2,fma`)
+//! with it, so that the listed features are removed whenever the cache is
+//! filled, including by the run-time dispatch of `coresimd` itself.
+
+use std::env;
+use std::sync::{Once, ONCE_INIT};
+
+use coresimd::vendor::{self, DetectedFeatures, Feature, MicroarchLevel,
+                       __Feature};
+
+/// This macro performs the run-time detection of a feature, or of an x86-64
+/// microarchitecture level.
+///
+/// PLEASE: do not use this, it is an implementation detail subject to change.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __unstable_detect_feature {
+    ("x86-64-v1") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V1)
+    };
+    ("x86-64-v2") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V2)
+    };
+    ("x86-64-v3") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V3)
+    };
+    ("x86-64-v4") => {
+        $crate::vendor::__unstable_detect_level(
+            $crate::vendor::MicroarchLevel::V4)
+    };
+    ($t:tt) => {
+        $crate::vendor::__unstable_detect_feature(__unstable_feature!($t))
+    };
+}
+
+/// Reports the value of `STDSIMD_DISABLE` to `coresimd`.
+fn disabled_env(report: &mut FnMut(&str)) {
+    if let Ok(list) = env::var("STDSIMD_DISABLE") {
+        report(&list);
+    }
+}
+
+/// Registers `disabled_env` with `coresimd`.
+extern "C" fn register() {
+    vendor::__unstable_set_disable_hook(disabled_env);
+}
+
+/// Runs `register` before `main`, and so before the first feature query of
+/// the process.
+#[used]
+#[cfg_attr(any(target_os = "linux", target_os = "android",
+               target_os = "freebsd", target_os = "dragonfly",
+               target_os = "netbsd", target_os = "openbsd"),
+           link_section = ".init_array")]
+#[cfg_attr(any(target_os = "macos", target_os = "ios"),
+           link_section = "__DATA,__mod_init_func")]
+#[cfg_attr(windows, link_section = ".CRT$XCU")]
+static REGISTER: extern "C" fn() = register;
+
+/// Registers `disabled_env`, on the first call only, on the targets where
+/// `REGISTER` does not run before `main`.
+fn register_once() {
+    static ONCE: Once = ONCE_INIT;
+    ONCE.call_once(|| register());
+}
+
+/// Performs run-time feature detection.
+#[doc(hidden)]
+pub fn __unstable_detect_feature(x: __Feature) -> bool {
+    register_once();
+    vendor::__unstable_detect_feature(x)
+}
+
+/// Performs run-time detection of all the features of `bits`, skipping the
+/// `None` entries, which are enabled at compile-time.
+#[doc(hidden)]
+pub fn __unstable_detect_all(bits: &[Option<u32>]) -> bool {
+    register_once();
+    vendor::__unstable_detect_all(bits)
+}
+
+/// Performs run-time detection of any of the features of `bits`.
+#[doc(hidden)]
+pub fn __unstable_detect_any(bits: &[u32]) -> bool {
+    register_once();
+    vendor::__unstable_detect_any(bits)
+}
+
+/// Performs run-time detection of a microarchitecture level.
+#[doc(hidden)]
+pub fn __unstable_detect_level(level: MicroarchLevel) -> bool {
+    register_once();
+    vendor::__unstable_detect_level(level)
+}
+
+/// Is `feature` supported by the host CPU?
+///
+/// Unlike `cfg_feature_enabled!`, this always queries the run-time cache,
+/// even for features enabled at compile-time.
+pub fn is_detected(feature: Feature) -> bool {
+    register_once();
+    vendor::is_detected(feature)
+}
+
+/// Returns an iterator over the features supported by the host CPU.
+pub fn detected_features() -> DetectedFeatures {
+    register_once();
+    vendor::detected_features()
+}
+
+/// Returns the highest x86-64 microarchitecture level supported by the
+/// host CPU, or `None` if it does not even support SSE2.
+pub fn microarch_level() -> Option<MicroarchLevel> {
+    register_once();
+    vendor::microarch_level()
+}
@@ -0,0 +1,34 @@
+//! `STDSIMD_DISABLE` masks features out of run-time detection.
+//!
+//! This is its own test binary: the variable is only read by the first
+//! feature query of the process, whether it is made by the user or by the
+//! run-time dispatch of the crate.
+
+#![feature(cfg_target_feature)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![cfg_attr(feature = "cargo-clippy", allow(option_unwrap_used))]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[macro_use]
+extern crate stdsimd;
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn disable_env() {
+    use std::env;
+    use stdsimd::simd::{f16x4, f32x4, u16x4};
+    use stdsimd::vendor::{detected_features, is_detected, Feature};
+
+    env::set_var("STDSIMD_DISABLE", "avx2, fma,unknown");
+    // The first query is made by the `f16c` dispatch of the conversion.
+    let half = f16x4::from_bits(u16x4::splat(0x3c00));
+    assert_eq!(half.to_f32(), f32x4::splat(1.0));
+    let avx2: Feature = "avx2".parse().unwrap();
+    let fma: Feature = "fma".parse().unwrap();
+    assert!(!is_detected(avx2));
+    assert!(!is_detected(fma));
+    assert!(detected_features().all(|f| f != avx2 && f != fma));
+    // Features enabled at compile-time are still reported.
+    assert_eq!(cfg_feature_enabled!("avx2"), cfg!(target_feature = "avx2"));
+    assert_eq!(cfg_feature_enabled!("fma"), cfg!(target_feature = "fma"));
+}
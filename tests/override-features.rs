@@ -0,0 +1,35 @@
+//! `override_features` masks features out of run-time detection.
+//!
+//! This is its own test binary: the override only takes effect before the
+//! first feature query of the process.
+
+#![feature(cfg_target_feature)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![cfg_attr(feature = "cargo-clippy", allow(option_unwrap_used))]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[macro_use]
+extern crate stdsimd;
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn override_features() {
+    use stdsimd::vendor::{is_detected, override_features, Feature};
+
+    let sse42: Feature = "sse4.2".parse().unwrap();
+    let popcnt: Feature = "popcnt".parse().unwrap();
+    assert!(override_features(vec![sse42, popcnt]));
+    assert!(!is_detected(sse42));
+    assert_eq!(
+        cfg_feature_enabled!("sse4.2"),
+        cfg!(target_feature = "sse4.2")
+    );
+    assert_eq!(
+        cfg_feature_enabled!("popcnt"),
+        cfg!(target_feature = "popcnt")
+    );
+    // Too late: the features have been detected.
+    let sse2: Feature = "sse2".parse().unwrap();
+    assert!(!override_features(vec![sse2]));
+    assert_eq!(is_detected(sse2), cfg_feature_enabled!("sse2"));
+}